@@ -0,0 +1,53 @@
+//! Compare saturating integer casts with `as` in a hot loop.
+//!
+//! Run with `cargo bench --bench saturating`.
+
+// `black_box` needs a newer Rust than this crate does
+#![allow(clippy::incompatible_msrv)]
+
+extern crate asprim;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use asprim::AsPrim;
+
+const LEN: usize = 1 << 16;
+const ROUNDS: u32 = 200;
+
+/// Time `f` over `src` and return nanoseconds per element.
+fn time<T: Copy, U: Copy + Default>(src: &[T], f: fn(T) -> U) -> f64 {
+    let mut dst = vec![U::default(); src.len()];
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for (d, &s) in dst.iter_mut().zip(black_box(src)) {
+            *d = f(s);
+        }
+        black_box(&mut dst);
+    }
+    start.elapsed().as_nanos() as f64 / (ROUNDS as f64 * src.len() as f64)
+}
+
+fn compare<T: AsPrim, U: AsPrim>(name: &str, src: &[T], wrap: fn(T) -> U, sat: fn(T) -> U) {
+    println!("{:<12} as: {:6.3} ns  saturating_as: {:6.3} ns",
+             name, time(src, wrap), time(src, sat));
+}
+
+fn main() {
+    // A cheap pseudo random sequence, so that branches would mispredict
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let src: Vec<i64> = (0..LEN).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state as i64) >> 40
+    }).collect();
+    let src32: Vec<i32> = src.iter().map(|&x| x as i32).collect();
+    let src_u32: Vec<u32> = src.iter().map(|&x| x as u32 >> 8).collect();
+
+    compare("i32 -> u8", &src32, |x| x as u8, |x| x.saturating_as::<u8>());
+    compare("i32 -> i16", &src32, |x| x as i16, |x| x.saturating_as::<i16>());
+    compare("i64 -> i32", &src, |x| x as i32, |x| x.saturating_as::<i32>());
+    compare("i64 -> u16", &src, |x| x as u16, |x| x.saturating_as::<u16>());
+    compare("u32 -> i8", &src_u32, |x| x as i8, |x| x.saturating_as::<i8>());
+}
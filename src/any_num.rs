@@ -0,0 +1,181 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+use cmp::fits;
+use {total_cmp_as, AsPrim, PrimValue, Signed, Unsigned};
+
+/// A value of any primitive numeric type, ordered and compared by its exact
+/// value across types, so that values of mixed types can be map keys.
+///
+/// The order is the one of `total_cmp_as`: integers and floats compare by
+/// their exact values, `-0.0` is less than `0.0`, and NaN with the sign bit
+/// set is less, and other NaN greater, than every number. Values that are
+/// not less or greater are equal, and hash equal: `5u8`, `5i64` and `5.0f32`
+/// are the same key. NaNs are equal only to NaNs with the same bits, as an
+/// `f64`.
+///
+/// ```
+/// use std::collections::{BTreeSet, HashSet};
+/// use asprim::AnyNum;
+///
+/// let mut keys = BTreeSet::new();
+/// keys.insert(AnyNum::from(5u8));
+/// keys.insert(AnyNum::from(5.0f32));
+/// keys.insert(AnyNum::from(-1i64));
+/// keys.insert(AnyNum::from(0.5));
+/// assert_eq!(keys.len(), 3);
+/// assert!(keys.contains(&AnyNum::from(5i32)));
+/// assert_eq!(keys.iter().next(), Some(&AnyNum::from(-1i8)));
+///
+/// let hashed: HashSet<_> = keys.iter().cloned().collect();
+/// assert!(hashed.contains(&AnyNum::from(5.0)));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct AnyNum(pub PrimValue);
+
+/// The canonical form of a value: integers, and floats equal to one, as
+/// `Signed` if negative and `Unsigned` if not, and other floats as `f64`.
+#[derive(Copy, Clone)]
+enum Key {
+    Signed(Signed),
+    Unsigned(Unsigned),
+    Float(f64),
+}
+
+impl Key {
+    fn of(value: PrimValue) -> Self {
+        let x = value.as_::<f64>();
+        if value.kind().is_float() && (x.is_nan() || x.is_sign_negative() && x == 0.) {
+            return Key::Float(x);
+        }
+        // An integer always fits the widest type of its sign
+        let float = value.kind().is_float();
+        if x >= 0. {
+            let u = value.as_::<Unsigned>();
+            if !float || fits::<Unsigned, _>(x) && u.as_f64() == x {
+                return Key::Unsigned(u);
+            }
+        } else {
+            let i = value.as_::<Signed>();
+            if !float || fits::<Signed, _>(x) && i.as_f64() == x {
+                return Key::Signed(i);
+            }
+        }
+        Key::Float(x)
+    }
+}
+
+impl AnyNum {
+    /// Wrap `x`.
+    #[inline]
+    pub fn new<P: AsPrim>(x: P) -> Self {
+        AnyNum(PrimValue::of(x))
+    }
+
+    /// The wrapped value.
+    #[inline]
+    pub fn value(self) -> PrimValue {
+        self.0
+    }
+}
+
+impl<P: AsPrim> From<P> for AnyNum {
+    #[inline]
+    fn from(x: P) -> Self {
+        AnyNum::new(x)
+    }
+}
+
+impl From<PrimValue> for AnyNum {
+    #[inline]
+    fn from(x: PrimValue) -> Self {
+        AnyNum(x)
+    }
+}
+
+impl Ord for AnyNum {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (Key::of(self.0), Key::of(other.0)) {
+            (Key::Signed(a), Key::Signed(b)) => a.cmp(&b),
+            (Key::Signed(_), Key::Unsigned(_)) => Ordering::Less,
+            (Key::Unsigned(_), Key::Signed(_)) => Ordering::Greater,
+            (Key::Unsigned(a), Key::Unsigned(b)) => a.cmp(&b),
+            (Key::Signed(a), Key::Float(b)) => total_cmp_as(a, b),
+            (Key::Unsigned(a), Key::Float(b)) => total_cmp_as(a, b),
+            (Key::Float(a), Key::Signed(b)) => total_cmp_as(a, b),
+            (Key::Float(a), Key::Unsigned(b)) => total_cmp_as(a, b),
+            (Key::Float(a), Key::Float(b)) => total_cmp_as(a, b),
+        }
+    }
+}
+
+impl PartialOrd for AnyNum {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for AnyNum {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AnyNum {}
+
+impl Hash for AnyNum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match Key::of(self.0) {
+            Key::Signed(i) => i.hash(state),
+            Key::Unsigned(u) => u.hash(state),
+            Key::Float(x) => x.to_bits().hash(state),
+        }
+    }
+}
+
+impl fmt::Display for AnyNum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let sorted = [
+            AnyNum::from(-f64::NAN),
+            AnyNum::from(f32::NEG_INFINITY),
+            AnyNum::from(i64::MIN),
+            AnyNum::from(-1.5),
+            AnyNum::from(-1i8),
+            AnyNum::from(-0.0f32),
+            AnyNum::from(0u8),
+            AnyNum::from(0.5f32),
+            AnyNum::from(u64::MAX),
+            AnyNum::from(1e30),
+            AnyNum::from(f64::INFINITY),
+            AnyNum::from(f32::NAN),
+        ];
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{} {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn equal_across_types() {
+        assert_eq!(AnyNum::from(0.0f64), AnyNum::from(0u32));
+        assert_eq!(AnyNum::from(-3i16), AnyNum::from(-3.0f32));
+        assert_eq!(AnyNum::from(u32::MAX), AnyNum::from(u32::MAX as f64));
+        assert!(AnyNum::from(u64::MAX) != AnyNum::from(u64::MAX as f64));
+        assert_eq!(AnyNum::from(f32::NAN), AnyNum::from(f32::NAN as f64));
+        assert!(AnyNum::from(-0.0) != AnyNum::from(0.0));
+    }
+}
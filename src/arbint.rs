@@ -0,0 +1,71 @@
+//! `AsPrim` for the integer types of `arbitrary-int`, like `u12` and `i24`.
+//!
+//! Each type casts through its underlying primitive. Casts to it wrap to
+//! its width like `as` does, sign-extending the signed types.
+
+use arbitrary_int::traits::Integer;
+
+macro_rules! arbint_impl {
+    ($repr:ty: $($t:ident)*) => {
+        $(
+            impl_as_prim!(::arbitrary_int::$t, repr: $repr,
+                          into: Integer::value,
+                          from: Integer::masked_new,
+                          min: <::arbitrary_int::$t as Integer>::MIN.value(),
+                          max: <::arbitrary_int::$t as Integer>::MAX.value(),
+                          boundary_values: &[<::arbitrary_int::$t as Integer>::MIN,
+                                             <::arbitrary_int::$t as Integer>::MAX]);
+        )*
+    }
+}
+
+arbint_impl!{u8: u1 u2 u3 u4 u5 u6 u7}
+arbint_impl!{u16: u9 u10 u11 u12 u13 u14 u15}
+arbint_impl!{u32: u17 u18 u19 u20 u21 u22 u23 u24 u25 u26 u27 u28 u29 u30 u31}
+arbint_impl!{u64: u33 u34 u35 u36 u37 u38 u39 u40 u41 u42 u43 u44 u45 u46 u47
+                  u48 u49 u50 u51 u52 u53 u54 u55 u56 u57 u58 u59 u60 u61 u62 u63}
+#[cfg(feature = "i128")]
+arbint_impl!{u128: u65 u66 u67 u68 u69 u70 u71 u72 u73 u74 u75 u76 u77 u78 u79
+                   u80 u81 u82 u83 u84 u85 u86 u87 u88 u89 u90 u91 u92 u93 u94 u95
+                   u96 u97 u98 u99 u100 u101 u102 u103 u104 u105 u106 u107 u108
+                   u109 u110 u111 u112 u113 u114 u115 u116 u117 u118 u119 u120
+                   u121 u122 u123 u124 u125 u126 u127}
+
+arbint_impl!{i8: i1 i2 i3 i4 i5 i6 i7}
+arbint_impl!{i16: i9 i10 i11 i12 i13 i14 i15}
+arbint_impl!{i32: i17 i18 i19 i20 i21 i22 i23 i24 i25 i26 i27 i28 i29 i30 i31}
+arbint_impl!{i64: i33 i34 i35 i36 i37 i38 i39 i40 i41 i42 i43 i44 i45 i46 i47
+                  i48 i49 i50 i51 i52 i53 i54 i55 i56 i57 i58 i59 i60 i61 i62 i63}
+#[cfg(feature = "i128")]
+arbint_impl!{i128: i65 i66 i67 i68 i69 i70 i71 i72 i73 i74 i75 i76 i77 i78 i79
+                   i80 i81 i82 i83 i84 i85 i86 i87 i88 i89 i90 i91 i92 i93 i94 i95
+                   i96 i97 i98 i99 i100 i101 i102 i103 i104 i105 i106 i107 i108
+                   i109 i110 i111 i112 i113 i114 i115 i116 i117 i118 i119 i120
+                   i121 i122 i123 i124 i125 i126 i127}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary_int::{i12, u12, u24};
+    use AsPrim;
+
+    #[test]
+    fn casts() {
+        assert_eq!(4095.as_::<u12>(), u12::new(4095));
+        assert_eq!(4096.as_::<u12>(), u12::new(0));
+        assert_eq!((-1).checked_as::<u12>(), None);
+        assert_eq!(1e9.saturating_as::<u24>(), u24::new(0xff_ffff));
+        assert_eq!(2048.as_::<i12>(), i12::new(-2048));
+        assert_eq!(i12::new(-5).as_f32(), -5.);
+        assert_eq!((-3000).saturating_as::<i12>(), i12::new(-2048));
+        assert_eq!(u12::new(4095).as_::<i12>(), i12::new(-1));
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn verify() {
+        use test_util::verify_asprim_impl;
+
+        verify_asprim_impl::<u12>(&[u12::new(0), u12::new(1), u12::new(4095)]);
+        verify_asprim_impl::<i12>(&[i12::new(-2048), i12::new(-1), i12::new(0), i12::new(2047)]);
+    }
+}
@@ -0,0 +1,100 @@
+//! Casts to and from the atomic types of `portable-atomic`.
+
+use portable_atomic::Ordering;
+use portable_atomic::{AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize};
+use portable_atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
+use portable_atomic::{AtomicF32, AtomicF64};
+#[cfg(feature = "i128")]
+use portable_atomic::{AtomicI128, AtomicU128};
+
+use AsPrim;
+
+/// Load and store an atomic primitive with a cast, using `as`.
+///
+/// It is implemented for the atomic types of `portable-atomic`, which
+/// exist for every width on every target, also those without native
+/// atomics of that width, like `thumbv6m`. There, `portable-atomic`
+/// needs one of its features `critical-section` or
+/// `unsafe-assume-single-core`, chosen by the final binary.
+///
+/// Requires the `portable-atomic` feature.
+///
+/// ```
+/// extern crate asprim;
+/// extern crate portable_atomic;
+///
+/// use portable_atomic::{AtomicU64, Ordering};
+/// use asprim::{AsPrim, AtomicAs};
+///
+/// # fn main() {
+/// static TOTAL: AtomicU64 = AtomicU64::new(0);
+///
+/// TOTAL.store_from(1.5e3f32, Ordering::Relaxed);
+/// assert_eq!(TOTAL.load_as::<f64>(Ordering::Relaxed), 1500.);
+///
+/// // Saturate first for a clamping store
+/// TOTAL.store_from((-1i32).saturating_as::<u64>(), Ordering::Relaxed);
+/// assert_eq!(TOTAL.load_as::<i8>(Ordering::Relaxed), 0);
+/// # }
+/// ```
+pub trait AtomicAs {
+    /// The primitive type of the value.
+    type Prim: AsPrim;
+
+    /// Load the value and cast it to `U`.
+    fn load_as<U: AsPrim>(&self, order: Ordering) -> U;
+
+    /// Cast `x` to the primitive type and store it.
+    fn store_from<T: AsPrim>(&self, x: T, order: Ordering);
+}
+
+macro_rules! atomic_as_impl {
+    ($($(#[$attr:meta])* $atomic:ident $t:ident)*) => {
+        $(
+            $(#[$attr])*
+            impl AtomicAs for $atomic {
+                type Prim = $t;
+
+                #[inline]
+                fn load_as<U: AsPrim>(&self, order: Ordering) -> U {
+                    self.load(order).as_()
+                }
+
+                #[inline]
+                fn store_from<T: AsPrim>(&self, x: T, order: Ordering) {
+                    self.store(x.as_(), order)
+                }
+            }
+        )*
+    }
+}
+
+atomic_as_impl!{
+    AtomicU8 u8 AtomicI8 i8 AtomicU16 u16 AtomicI16 i16
+    AtomicU32 u32 AtomicI32 i32 AtomicU64 u64 AtomicI64 i64
+    #[cfg(feature = "i128")] AtomicU128 u128
+    #[cfg(feature = "i128")] AtomicI128 i128
+    AtomicUsize usize AtomicIsize isize AtomicF32 f32 AtomicF64 f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_store() {
+        let x = AtomicI16::new(0);
+        x.store_from(40000u32, Ordering::SeqCst);
+        assert_eq!(x.load(Ordering::SeqCst), 40000u32 as i16);
+        assert_eq!(x.load_as::<u16>(Ordering::SeqCst), 40000);
+        let f = AtomicF32::new(0.);
+        f.store_from(u64::MAX, Ordering::SeqCst);
+        assert_eq!(f.load_as::<u64>(Ordering::SeqCst), u64::MAX);
+        #[cfg(feature = "i128")]
+        {
+            let big = AtomicU128::new(0);
+            big.store_from(-1i8, Ordering::SeqCst);
+            assert_eq!(big.load_as::<u128>(Ordering::SeqCst), u128::MAX);
+        }
+    }
+}
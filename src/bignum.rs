@@ -0,0 +1,177 @@
+//! Conversions between `rug::Integer` and `rug::Float` and primitive
+//! types, with the rounding mode of each inexact step given explicitly.
+//!
+//! Requires the `rug` feature.
+//!
+//! ```no_run
+//! extern crate asprim;
+//! extern crate rug;
+//!
+//! use std::cmp::Ordering;
+//! use rug::float::Round;
+//! use rug::{Float, Integer};
+//! use asprim::bignum::{from_float, from_integer, to_float, to_integer};
+//!
+//! # fn main() {
+//! // 0.1 is rounded up in f64, and exact in a wider `Float`
+//! let (tenth, ord) = to_float(0.1f64, 200, Round::Nearest);
+//! assert_eq!(ord, Ordering::Equal);
+//! assert!(tenth > Float::with_val(200, 1) / 10);
+//!
+//! let third = Float::with_val(200, 1) / 3;
+//! assert_eq!(from_float::<f32>(&third, Round::Up), Some(0.33333334));
+//! assert_eq!(from_float::<u8>(&(third * 3000), Round::Up), None);
+//!
+//! let big = Integer::from(u64::MAX);
+//! assert_eq!(from_integer::<f32>(&big, Round::Zero), Some(1.8446743e19));
+//! assert_eq!(to_integer(-2.5f32, Round::Nearest), Some((Integer::from(-2), Ordering::Greater)));
+//! # }
+//! ```
+
+use core::cmp::Ordering;
+
+use rug::float::Round;
+use rug::{Float, Integer};
+
+use {AsPrim, PrimKind, PrimValue};
+
+/// Convert `x` to a `Float` of precision `prec`, rounding with `round`,
+/// and return it with its order relative to `x`.
+pub fn to_float<T: AsPrim>(x: T, prec: u32, round: Round) -> (Float, Ordering) {
+    match PrimValue::of(x) {
+        PrimValue::U8(x) => Float::with_val_round(prec, x, round),
+        PrimValue::I8(x) => Float::with_val_round(prec, x, round),
+        PrimValue::U16(x) => Float::with_val_round(prec, x, round),
+        PrimValue::I16(x) => Float::with_val_round(prec, x, round),
+        PrimValue::U32(x) => Float::with_val_round(prec, x, round),
+        PrimValue::I32(x) => Float::with_val_round(prec, x, round),
+        PrimValue::U64(x) => Float::with_val_round(prec, x, round),
+        PrimValue::I64(x) => Float::with_val_round(prec, x, round),
+        #[cfg(feature = "i128")]
+        PrimValue::U128(x) => Float::with_val_round(prec, x, round),
+        #[cfg(feature = "i128")]
+        PrimValue::I128(x) => Float::with_val_round(prec, x, round),
+        PrimValue::Usize(x) => Float::with_val_round(prec, x, round),
+        PrimValue::Isize(x) => Float::with_val_round(prec, x, round),
+        PrimValue::F32(x) => Float::with_val_round(prec, x, round),
+        PrimValue::F64(x) => Float::with_val_round(prec, x, round),
+    }
+}
+
+/// Convert `x` to an `Integer`, rounding floats to an integer with
+/// `round`, and return it with its order relative to `x`, or return `None`
+/// if `x` is NaN or infinite.
+pub fn to_integer<T: AsPrim>(x: T, round: Round) -> Option<(Integer, Ordering)> {
+    if T::KIND.is_float() {
+        // Floats are exact in a `Float` of their precision
+        return Float::with_val(T::KIND.mantissa_digits(), x.as_f64()).to_integer_round(round);
+    }
+    let n = match PrimValue::of(x) {
+        PrimValue::U8(x) => Integer::from(x),
+        PrimValue::I8(x) => Integer::from(x),
+        PrimValue::U16(x) => Integer::from(x),
+        PrimValue::I16(x) => Integer::from(x),
+        PrimValue::U32(x) => Integer::from(x),
+        PrimValue::I32(x) => Integer::from(x),
+        PrimValue::U64(x) => Integer::from(x),
+        PrimValue::I64(x) => Integer::from(x),
+        #[cfg(feature = "i128")]
+        PrimValue::U128(x) => Integer::from(x),
+        #[cfg(feature = "i128")]
+        PrimValue::I128(x) => Integer::from(x),
+        PrimValue::Usize(x) => Integer::from(x),
+        PrimValue::Isize(x) => Integer::from(x),
+        PrimValue::F32(_) | PrimValue::F64(_) => unreachable!(),
+    };
+    Some((n, Ordering::Equal))
+}
+
+/// Convert `n` to the type `T`, or return `None` if it is out of range.
+///
+/// Conversions to a float type round with `round`, and are out of range
+/// if the result overflows to infinity; rounding toward zero clamps to the
+/// largest finite value instead.
+pub fn from_integer<T: AsPrim>(n: &Integer, round: Round) -> Option<T> {
+    match T::KIND {
+        PrimKind::U8 => n.to_u8().map(T::cast_from),
+        PrimKind::I8 => n.to_i8().map(T::cast_from),
+        PrimKind::U16 => n.to_u16().map(T::cast_from),
+        PrimKind::I16 => n.to_i16().map(T::cast_from),
+        PrimKind::U32 => n.to_u32().map(T::cast_from),
+        PrimKind::I32 => n.to_i32().map(T::cast_from),
+        PrimKind::U64 => n.to_u64().map(T::cast_from),
+        PrimKind::I64 => n.to_i64().map(T::cast_from),
+        #[cfg(feature = "i128")]
+        PrimKind::U128 => n.to_u128().map(T::cast_from),
+        #[cfg(feature = "i128")]
+        PrimKind::I128 => n.to_i128().map(T::cast_from),
+        PrimKind::Usize => n.to_usize().map(T::cast_from),
+        PrimKind::Isize => n.to_isize().map(T::cast_from),
+        PrimKind::F32 | PrimKind::F64 => {
+            let (f, _) = Float::with_val_round(T::KIND.mantissa_digits(), n, round);
+            from_float(&f, round)
+        }
+    }
+}
+
+/// Convert `f` to the type `T`, rounding with `round`, or return `None` if
+/// it is out of range.
+///
+/// Conversions to an integer type round to an integer, and fail for NaN
+/// and the infinities. Conversions to a float type keep NaN and the
+/// infinities, and fail if a finite value overflows to infinity.
+pub fn from_float<T: AsPrim>(f: &Float, round: Round) -> Option<T> {
+    let y = match T::KIND {
+        PrimKind::F32 => f.to_f32_round(round) as f64,
+        PrimKind::F64 => f.to_f64_round(round),
+        _ => {
+            let (n, _) = f.to_integer_round(round)?;
+            return from_integer(&n, round);
+        }
+    };
+    if f.is_finite() && y.is_infinite() {
+        None
+    } else {
+        Some(T::cast_from(y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        for &x in &[i64::MIN, -1, 0, i64::MAX] {
+            let (n, ord) = to_integer(x, Round::Nearest).unwrap();
+            assert_eq!(ord, Ordering::Equal);
+            assert_eq!(from_integer::<i64>(&n, Round::Nearest), Some(x));
+        }
+        let n = Integer::from(u64::MAX) + 1;
+        assert_eq!(from_integer::<u64>(&n, Round::Nearest), None);
+        assert_eq!(from_integer::<f64>(&n, Round::Nearest), Some(2f64.powi(64)));
+        assert_eq!(from_integer::<f64>(&(n.clone() + 1), Round::Up),
+                   Some(2f64.powi(64) + 4096.));
+        assert_eq!(from_integer::<f32>(&(Integer::from(1) << 128), Round::Nearest), None);
+        assert_eq!(from_integer::<f32>(&(Integer::from(1) << 128), Round::Zero), Some(f32::MAX));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(to_integer(f64::NAN, Round::Nearest), None);
+        assert_eq!(to_integer(1.5f32, Round::Nearest), Some((Integer::from(2), Ordering::Greater)));
+        assert_eq!(to_integer(1.5f64, Round::Zero), Some((Integer::from(1), Ordering::Less)));
+        let (f, ord) = to_float(u64::MAX, 53, Round::Down);
+        assert_eq!(ord, Ordering::Less);
+        assert_eq!(from_float::<u64>(&f, Round::Nearest), Some(u64::MAX - 2047));
+        let (nan, _) = to_float(f32::NAN, 24, Round::Nearest);
+        assert!(from_float::<f64>(&nan, Round::Nearest).unwrap().is_nan());
+        assert_eq!(from_float::<i32>(&nan, Round::Nearest), None);
+        let huge = Float::with_val(53, 1e300);
+        assert_eq!(from_float::<f32>(&huge, Round::Nearest), None);
+        assert_eq!(from_float::<f64>(&huge, Round::Nearest), Some(1e300));
+        let tiny = Float::with_val(53, 1e-300);
+        assert_eq!(from_float::<f32>(&tiny, Round::Up), Some(f32::from_bits(1)));
+        assert_eq!(from_float::<f32>(&tiny, Round::Down), Some(0.));
+    }
+}
@@ -0,0 +1,138 @@
+use {AsPrim, Float, Unsigned};
+
+// The index of float values for `index`, computed in `$float`
+macro_rules! float_index {
+    ($name:ident, $float:ty) => {
+        fn $name<P: AsPrim>(value: P, min: P, max: P, bins: usize) -> Option<usize> {
+            let value = value.as_::<$float>();
+            let (min, max) = (min.as_::<$float>(), max.as_::<$float>());
+            // In `f32`, distinct f64 bounds may round to the same value
+            if min >= max {
+                return None;
+            }
+            let (mut offset, mut width) = (value - min, max - min);
+            if width.is_infinite() {
+                // Halving is exact for such large values, and keeps the width
+                // of a range of finite bounds finite
+                offset = value / 2. - min / 2.;
+                width = max / 2. - min / 2.;
+                if width.is_infinite() {
+                    return None;
+                }
+            }
+            Some((offset / width * bins.as_::<$float>()).as_usize())
+        }
+    }
+}
+
+float_index!{float_index, Float}
+float_index!{float_index_f32, f32}
+
+#[inline]
+fn index<P: AsPrim>(value: P, min: P, max: P, bins: usize,
+                    float_index: fn(P, P, P, usize) -> Option<usize>)
+    -> Option<usize>
+{
+    // Also false if any of them is NaN
+    if !(min < max && min <= value && value <= max) || bins == 0 {
+        return None;
+    }
+    let last = bins - 1;
+    let index = if P::KIND.is_float() {
+        float_index(value, min, max, bins)?
+    } else {
+        // Differences of integers of the same type fit in the unsigned type
+        let offset = value.as_::<Unsigned>().wrapping_sub(min.as_());
+        let width = max.as_::<Unsigned>().wrapping_sub(min.as_());
+        match offset.checked_mul(bins.as_()) {
+            Some(scaled) => (scaled / width).as_usize(),
+            None => (offset.as_f64() / width.as_f64() * bins.as_f64()).as_usize(),
+        }
+    };
+    Some(if index > last { last } else { index })
+}
+
+/// Return the index of the bin that `value` falls in, when the range from
+/// `min` to `max` is split into `bins` bins of equal width.
+///
+/// Each bin includes its lower edge, and the last bin also includes `max`.
+/// Return `None` if `value` is outside the range or NaN, if `min` is not
+/// less than `max`, if either is infinite, or if `bins` is zero.
+///
+/// Float values are computed in `f64`, or in `f32` with the `prefer-f32`
+/// feature; `bin_index_f32` always uses `f32`.
+///
+/// ```
+/// use asprim::bin_index;
+///
+/// assert_eq!(bin_index(0.5, 0., 2., 4), Some(1));
+/// assert_eq!(bin_index(2., 0., 2., 4), Some(3));
+/// assert_eq!(bin_index(2.5, 0., 2., 4), None);
+/// assert_eq!(bin_index(255u8, 0, 255, 2), Some(1));
+/// assert_eq!(bin_index(-1i32, -10, 10, 3), Some(1));
+/// ```
+pub fn bin_index<P: AsPrim>(value: P, min: P, max: P, bins: usize) -> Option<usize> {
+    index(value, min, max, bins, float_index)
+}
+
+/// Return the index of the bin that `value` falls in, like `bin_index`, but
+/// compute it in `f32` for float values.
+///
+/// For targets with only a single precision FPU. Unlike the `prefer-f32`
+/// feature, it does not change `bin_index` for the other crates that use
+/// it. `None` is also returned when `f64` bounds round to the same `f32`,
+/// or overflow it.
+///
+/// ```
+/// use asprim::bin_index_f32;
+///
+/// assert_eq!(bin_index_f32(0.5, 0., 2., 4), Some(1));
+/// assert_eq!(bin_index_f32(1., 1., 1. + 1e-12, 4), None);
+/// assert_eq!(bin_index_f32(7u8, 0, 255, 2), Some(0));
+/// ```
+pub fn bin_index_f32<P: AsPrim>(value: P, min: P, max: P, bins: usize) -> Option<usize> {
+    index(value, min, max, bins, float_index_f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges() {
+        assert_eq!(bin_index(f64::NAN, 0., 1., 10), None);
+        assert_eq!(bin_index(0.5, f64::NAN, 1., 10), None);
+        assert_eq!(bin_index(0.5, 0., 1., 0), None);
+        assert_eq!(bin_index(1, 1, 1, 10), None);
+        assert_eq!(bin_index(0., 0., 1., 10), Some(0));
+        assert_eq!(bin_index(0.1f32, 0., 1., 10), Some(1));
+        assert_eq!(bin_index(1.0 - f64::EPSILON, 0., 1., 10), Some(9));
+        assert_eq!(bin_index(i64::MAX, i64::MIN, i64::MAX, usize::MAX), Some(usize::MAX - 1));
+        assert_eq!(bin_index(0i64, i64::MIN, i64::MAX, 2), Some(1));
+        assert_eq!(bin_index(-1i64, i64::MIN, i64::MAX, 2), Some(0));
+        // The width of these ranges overflows
+        #[cfg(not(feature = "prefer-f32"))]
+        {
+            assert_eq!(bin_index(0., f64::MIN, f64::MAX, 4), Some(2));
+            assert_eq!(bin_index(-1e308, -1e308, 1e308, 4), Some(0));
+            assert_eq!(bin_index(0.6e308, -1e308, 1e308, 4), Some(3));
+            assert_eq!(bin_index(f64::MAX, f64::MIN, f64::MAX, 4), Some(3));
+        }
+        #[cfg(feature = "prefer-f32")]
+        {
+            assert_eq!(bin_index(1., 1., 1. + 1e-12, 4), None);
+            assert_eq!(bin_index(0., -1e300, 1e300, 4), None);
+            assert_eq!(bin_index(-1e37f32, f32::MIN, f32::MAX, 4), Some(1));
+        }
+        assert_eq!(bin_index_f32(1., 1., 1. + 1e-12, 4), None);
+        assert_eq!(bin_index_f32(0., -1e300, 1e300, 4), None);
+        assert_eq!(bin_index_f32(-1e37f32, f32::MIN, f32::MAX, 4), Some(1));
+        assert_eq!(bin_index_f32(0.1f64, 0., 1., 10), Some(1));
+        assert_eq!(bin_index_f32(i64::MAX, i64::MIN, i64::MAX, usize::MAX), Some(usize::MAX - 1));
+        assert_eq!(bin_index(0., f64::NEG_INFINITY, 1., 4), None);
+        assert_eq!(bin_index(0., 0., f64::INFINITY, 4), None);
+        for x in 0..100u8 {
+            assert_eq!(bin_index(x, 0, 100, 10), Some(x as usize / 10));
+        }
+    }
+}
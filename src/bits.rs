@@ -0,0 +1,106 @@
+use core::mem;
+
+use PrimInt;
+
+/// Bit operations of the primitive integer types.
+///
+/// The methods are the inherent methods of the same name.
+///
+/// ```
+/// use asprim::PrimBits;
+///
+/// fn set_bits<T: PrimBits>(mut x: T) -> Vec<u32> {
+///     let mut bits = Vec::new();
+///     let mut base = 0;
+///     while x.count_ones() > 0 {
+///         let n = x.trailing_zeros();
+///         bits.push(base + n);
+///         x = x.checked_shr(n + 1).unwrap_or_default();
+///         base += n + 1;
+///     }
+///     bits
+/// }
+///
+/// assert_eq!(set_bits(0b1010_0001u8), [0, 5, 7]);
+/// assert_eq!(set_bits(i64::MIN), [63]);
+/// ```
+pub trait PrimBits : PrimInt {
+    /// The size of the type in bits.
+    const BITS: u32;
+
+    fn count_ones(self) -> u32;
+    fn count_zeros(self) -> u32;
+    fn leading_zeros(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn rotate_left(self, n: u32) -> Self;
+    fn rotate_right(self, n: u32) -> Self;
+    fn swap_bytes(self) -> Self;
+    fn reverse_bits(self) -> Self;
+    /// Shift left, or return `None` if `n` is not less than `BITS`.
+    fn checked_shl(self, n: u32) -> Option<Self>;
+    /// Shift right, or return `None` if `n` is not less than `BITS`.
+    fn checked_shr(self, n: u32) -> Option<Self>;
+    /// Shift left by `n` modulo `BITS`.
+    fn wrapping_shl(self, n: u32) -> Self;
+    /// Shift right by `n` modulo `BITS`.
+    fn wrapping_shr(self, n: u32) -> Self;
+}
+
+macro_rules! prim_bits_impl {
+    ($($t:ty)*) => {
+        $(
+            impl PrimBits for $t {
+                const BITS: u32 = 8 * mem::size_of::<$t>() as u32;
+
+                prim_bits_impl!{@forward $t; count_ones u32, count_zeros u32,
+                                leading_zeros u32, trailing_zeros u32,
+                                swap_bytes $t, reverse_bits $t}
+                prim_bits_impl!{@forward_n $t; rotate_left $t, rotate_right $t,
+                                checked_shl Option<$t>, checked_shr Option<$t>,
+                                wrapping_shl $t, wrapping_shr $t}
+            }
+        )*
+    };
+    (@forward $t:ty; $($method:ident $ret:ty),*) => {
+        $(
+            #[inline(always)]
+            fn $method(self) -> $ret {
+                <$t>::$method(self)
+            }
+        )*
+    };
+    (@forward_n $t:ty; $($method:ident $ret:ty),*) => {
+        $(
+            #[inline(always)]
+            fn $method(self, n: u32) -> $ret {
+                <$t>::$method(self, n)
+            }
+        )*
+    };
+}
+
+prim_bits_impl!{u8 i8 u16 i16 u32 i32 u64 i64 usize isize}
+#[cfg(feature = "i128")]
+prim_bits_impl!{u128 i128}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ones<T: PrimBits>(x: T) -> (u32, u32) {
+        (x.count_ones(), x.count_zeros())
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(<u8 as PrimBits>::BITS, 8);
+        assert_eq!(<isize as PrimBits>::BITS as usize, 8 * core::mem::size_of::<isize>());
+        assert_eq!(ones(-1i16), (16, 0));
+        assert_eq!(ones(0x0fu32), (4, 28));
+        assert_eq!(PrimBits::checked_shl(1u8, 8), None);
+        assert_eq!(PrimBits::wrapping_shr(0x80u8, 15), 1);
+        assert_eq!(PrimBits::rotate_left(0x81u8, 1), 3);
+        #[cfg(feature = "i128")]
+        assert_eq!(PrimBits::leading_zeros(1u128), 127);
+    }
+}
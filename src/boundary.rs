@@ -0,0 +1,90 @@
+//! The values behind `AsPrim::BOUNDARY_VALUES`.
+
+pub trait BoundaryValues : 'static + Copy {
+    const VALUES: &'static [Self];
+}
+
+macro_rules! boundary_values {
+    ($($t:ty: [$($x:expr),*])*) => {
+        $(
+            impl BoundaryValues for $t {
+                const VALUES: &'static [$t] = &[$($x),*];
+            }
+        )*
+    }
+}
+
+// Integers: zero, ±1, the limits, and the first integers that f32 and f64
+// can not represent exactly.
+boundary_values!{
+    u8: [0, 1, u8::MAX]
+    i8: [0, 1, -1, i8::MIN, i8::MAX]
+    u16: [0, 1, u16::MAX]
+    i16: [0, 1, -1, i16::MIN, i16::MAX]
+    u32: [0, 1, u32::MAX, 1 << 24, (1 << 24) + 1]
+    i32: [0, 1, -1, i32::MIN, i32::MAX, 1 << 24, (1 << 24) + 1, -(1 << 24) - 1]
+    u64: [0, 1, u64::MAX, 1 << 24, (1 << 24) + 1, 1 << 53, (1 << 53) + 1]
+    i64: [0, 1, -1, i64::MIN, i64::MAX, 1 << 24, (1 << 24) + 1, -(1 << 24) - 1,
+          1 << 53, (1 << 53) + 1, -(1 << 53) - 1]
+}
+
+#[cfg(feature = "i128")]
+boundary_values!{
+    u128: [0, 1, u128::MAX, 1 << 24, (1 << 24) + 1, 1 << 53, (1 << 53) + 1]
+    i128: [0, 1, -1, i128::MIN, i128::MAX, 1 << 24, (1 << 24) + 1, -(1 << 24) - 1,
+           1 << 53, (1 << 53) + 1, -(1 << 53) - 1]
+}
+
+#[cfg(target_pointer_width = "64")]
+boundary_values!{
+    usize: [0, 1, usize::MAX, 1 << 24, (1 << 24) + 1, 1 << 53, (1 << 53) + 1]
+    isize: [0, 1, -1, isize::MIN, isize::MAX, 1 << 24, (1 << 24) + 1, -(1 << 24) - 1,
+            1 << 53, (1 << 53) + 1, -(1 << 53) - 1]
+}
+
+#[cfg(target_pointer_width = "32")]
+boundary_values!{
+    usize: [0, 1, usize::MAX, 1 << 24, (1 << 24) + 1]
+    isize: [0, 1, -1, isize::MIN, isize::MAX, 1 << 24, (1 << 24) + 1, -(1 << 24) - 1]
+}
+
+#[cfg(target_pointer_width = "16")]
+boundary_values!{
+    usize: [0, 1, usize::MAX]
+    isize: [0, 1, -1, isize::MIN, isize::MAX]
+}
+
+// Floats: signed zeros, ±1, the limits, the smallest normal value, the
+// largest exact integers of f32 and f64 (2^24 and 2^53) with a neighbour,
+// the integer ranges of i32, u32, i64 and u64, and the non-finite values.
+boundary_values!{
+    f32: [0., -0., 1., -1., f32::MIN, f32::MAX, f32::MIN_POSITIVE,
+          16777216., 16777218., -16777216.,
+          2147483648., -2147483648., 4294967296.,
+          9223372036854775808., -9223372036854775808., 18446744073709551616.,
+          f32::INFINITY, f32::NEG_INFINITY, f32::NAN]
+    f64: [0., -0., 1., -1., f64::MIN, f64::MAX, f64::MIN_POSITIVE,
+          16777216., 16777217., -16777216., 9007199254740992., 9007199254740994.,
+          -9007199254740992.,
+          2147483648., 2147483647.5, -2147483648., -2147483648.5, 4294967296.,
+          9223372036854775808., -9223372036854775808., 18446744073709551616.,
+          f64::INFINITY, f64::NEG_INFINITY, f64::NAN]
+}
+
+#[cfg(test)]
+mod tests {
+    use AsPrim;
+
+    // The same values in the same order, compared by their 64-bit pattern
+    fn same<T: AsPrim, U: AsPrim>() -> bool {
+        T::BOUNDARY_VALUES.iter().map(|x| x.as_u64()).eq(U::BOUNDARY_VALUES.iter().map(|x| x.as_u64()))
+    }
+
+    #[test]
+    fn pointer_sized() {
+        #[cfg(target_pointer_width = "64")]
+        assert!(same::<usize, u64>() && same::<isize, i64>());
+        #[cfg(target_pointer_width = "32")]
+        assert!(same::<usize, u32>() && same::<isize, i32>());
+    }
+}
@@ -0,0 +1,460 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use sealed::SealedBytes;
+use {AsPrim, PrimKind};
+
+/// The byte order of values in a byte buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endian = Endian::Little;
+    /// The byte order of the target.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endian = Endian::Big;
+}
+
+/// Conversion of the primitive numeric types to and from bytes.
+///
+/// The trait is sealed: only the primitive types implement it, so that
+/// `SIZE` is at most 16, and `Be` and `Le` can rely on `Raw` having the
+/// same size.
+///
+/// ```
+/// use asprim::{Endian, PrimBytes};
+///
+/// let mut buf = [0; 4];
+/// 0x1234_5678u32.write_bytes(&mut buf, Endian::Big);
+/// assert_eq!(buf, [0x12, 0x34, 0x56, 0x78]);
+/// assert_eq!(f32::read_bytes(&[0, 0, 0x80, 0x3f], Endian::Little), 1.);
+/// ```
+pub trait PrimBytes : AsPrim + SealedBytes {
+    /// The size of the type in bytes.
+    const SIZE: usize;
+
+    /// The unsigned integer type of the same size, which `Be` and `Le`
+    /// store the bytes of a value in.
+    type Raw: PrimBytes;
+
+    /// Read a value from exactly `SIZE` bytes.
+    ///
+    /// ***Panics*** if `bytes` does not have the length `SIZE`.
+    fn read_bytes(bytes: &[u8], endian: Endian) -> Self;
+
+    /// Write self to exactly `SIZE` bytes.
+    ///
+    /// ***Panics*** if `bytes` does not have the length `SIZE`.
+    fn write_bytes(self, bytes: &mut [u8], endian: Endian);
+}
+
+macro_rules! prim_bytes_impl {
+    ($($(#[$attr:meta])* $t:ident($raw:ident))*) => {
+        $(
+            $(#[$attr])*
+            impl SealedBytes for $t {}
+
+            $(#[$attr])*
+            impl PrimBytes for $t {
+                const SIZE: usize = mem::size_of::<$t>();
+                type Raw = $raw;
+
+                #[inline]
+                fn read_bytes(bytes: &[u8], endian: Endian) -> Self {
+                    let mut buf = [0; mem::size_of::<$t>()];
+                    buf.copy_from_slice(bytes);
+                    match endian {
+                        Endian::Little => $t::from_le_bytes(buf),
+                        Endian::Big => $t::from_be_bytes(buf),
+                    }
+                }
+
+                #[inline]
+                fn write_bytes(self, bytes: &mut [u8], endian: Endian) {
+                    bytes.copy_from_slice(&match endian {
+                        Endian::Little => self.to_le_bytes(),
+                        Endian::Big => self.to_be_bytes(),
+                    });
+                }
+            }
+        )*
+    }
+}
+
+prim_bytes_impl!{
+    u8(u8) i8(u8) u16(u16) i16(u16) u32(u32) i32(u32) u64(u64) i64(u64)
+    #[cfg(feature = "i128")] u128(u128)
+    #[cfg(feature = "i128")] i128(u128)
+    usize(usize) isize(usize) f32(u32) f64(u64)
+}
+
+/// Reinterpret the bytes of `x`, in the byte order `from`, as a value of
+/// `U` in the byte order `to`; `U` has the same size.
+///
+/// Only the bytes are moved, so a float never holds a swapped bit pattern,
+/// which could be a signaling NaN that loading it on x87 quiets.
+#[inline]
+fn convert<T: PrimBytes, U: PrimBytes>(x: T, from: Endian, to: Endian) -> U {
+    let mut buf = [0; 16];
+    let buf = &mut buf[..T::SIZE];
+    x.write_bytes(buf, from);
+    U::read_bytes(buf, to)
+}
+
+macro_rules! endian_wrapper {
+    ($(#[$doc:meta])* $name:ident, $endian:expr) => {
+        $(#[$doc])*
+        #[derive(Copy, Clone)]
+        #[repr(transparent)]
+        pub struct $name<T: PrimBytes>(T::Raw);
+
+        impl<T: PrimBytes> $name<T> {
+            /// Store `x` in this byte order.
+            #[inline]
+            pub fn new(x: T) -> Self {
+                $name(convert(x, $endian, Endian::NATIVE))
+            }
+
+            /// Return the value in the native byte order.
+            #[inline]
+            pub fn get(self) -> T {
+                convert(self.0, Endian::NATIVE, $endian)
+            }
+
+            /// Replace the value with `x`.
+            #[inline]
+            pub fn set(&mut self, x: T) {
+                *self = $name::new(x);
+            }
+        }
+
+        impl<T: PrimBytes> From<T> for $name<T> {
+            #[inline]
+            fn from(x: T) -> Self {
+                $name::new(x)
+            }
+        }
+
+        impl<T: PrimBytes> Default for $name<T> {
+            #[inline]
+            fn default() -> Self {
+                $name::new(T::default())
+            }
+        }
+
+        impl<T: PrimBytes> PartialEq for $name<T> {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.get() == other.get()
+            }
+        }
+
+        impl<T: PrimBytes + Eq> Eq for $name<T> {}
+
+        impl<T: PrimBytes> PartialOrd for $name<T> {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.get().partial_cmp(&other.get())
+            }
+        }
+
+        impl<T: PrimBytes + Ord> Ord for $name<T> {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl<T: PrimBytes + Hash> Hash for $name<T> {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.get().hash(state)
+            }
+        }
+
+        impl<T: PrimBytes> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.get()).finish()
+            }
+        }
+
+        impl<T: PrimBytes> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&self.get(), f)
+            }
+        }
+
+        impl<T: PrimBytes> AsPrim for $name<T> {
+            const KIND: PrimKind = T::KIND;
+            // A constant can't be built from those of `T`; see `AsPrim`
+            const BOUNDARY_VALUES: &'static [Self] = &[];
+            __asprim_delegate!{$name<T>, T, $name::get;
+                as_usize usize, as_isize isize,
+                as_u64 u64, as_i64 i64, as_u32 u32, as_i32 i32,
+                as_u16 u16, as_i16 i16, as_u8 u8, as_i8 i8,
+                as_f32 f32, as_f64 f64,
+                as_usize_saturating usize, as_isize_saturating isize,
+                as_u64_saturating u64, as_i64_saturating i64,
+                as_u32_saturating u32, as_i32_saturating i32,
+                as_u16_saturating u16, as_i16_saturating i16,
+                as_u8_saturating u8, as_i8_saturating i8,
+                as_f32_saturating f32, as_f64_saturating f64}
+            __asprim_delegate!{$name<T>, T, $name::get;
+                as_usize_checked Option<usize>, as_isize_checked Option<isize>,
+                as_u64_checked Option<u64>, as_i64_checked Option<i64>,
+                as_u32_checked Option<u32>, as_i32_checked Option<i32>,
+                as_u16_checked Option<u16>, as_i16_checked Option<i16>,
+                as_u8_checked Option<u8>, as_i8_checked Option<i8>,
+                as_f32_checked Option<f32>, as_f64_checked Option<f64>,
+                as_f32_exact Option<f32>, as_f64_exact Option<f64>}
+            #[cfg(feature = "i128")]
+            __asprim_delegate!{$name<T>, T, $name::get;
+                as_u128 u128, as_i128 i128,
+                as_u128_saturating u128, as_i128_saturating i128,
+                as_u128_checked Option<u128>, as_i128_checked Option<i128>}
+            #[inline]
+            fn cast_from<S: AsPrim>(x: S) -> Self {
+                $name::new(T::cast_from(x))
+            }
+            #[inline]
+            fn checked_cast_from<S: AsPrim>(x: S) -> Option<Self> {
+                T::checked_cast_from(x).map($name::new)
+            }
+            #[inline]
+            fn saturating_cast_from<S: AsPrim>(x: S) -> Self {
+                $name::new(T::saturating_cast_from(x))
+            }
+        }
+    }
+}
+
+endian_wrapper!{
+    /// A value of the primitive type `T` stored in big-endian byte order.
+    ///
+    /// The wrapper has the size and alignment of `T`, so it can describe
+    /// the fields of file formats and packets, and it implements `AsPrim`
+    /// with the value in native byte order, so generic numeric code reads
+    /// and writes it without swapping bytes by hand.
+    ///
+    /// ```
+    /// use asprim::{AsPrim, Be};
+    ///
+    /// #[repr(C)]
+    /// struct Header {
+    ///     length: Be<u32>,
+    ///     scale: Be<f32>,
+    /// }
+    ///
+    /// let header = Header { length: Be::new(0x0102), scale: 2.5.as_() };
+    /// let bytes: [u8; 4] = unsafe { std::mem::transmute(header.length) };
+    /// assert_eq!(bytes, [0, 0, 1, 2]);
+    /// assert_eq!(header.length.as_f64(), 258.);
+    /// assert_eq!(header.scale.get(), 2.5);
+    /// ```
+    Be, Endian::Big
+}
+
+endian_wrapper!{
+    /// A value of the primitive type `T` stored in little-endian byte order.
+    ///
+    /// This is the little-endian counterpart of `Be`.
+    ///
+    /// ```
+    /// use asprim::{AsPrim, Le};
+    ///
+    /// let x: Le<u16> = 300i32.as_();
+    /// let bytes: [u8; 2] = unsafe { std::mem::transmute(x) };
+    /// assert_eq!(bytes, [0x2c, 0x01]);
+    /// assert_eq!(x.checked_as::<u8>(), None);
+    /// ```
+    Le, Endian::Little
+}
+
+/// Read the tightly packed values of `bytes`, in the byte order `endian`,
+/// into `dst`.
+///
+/// ***Panics*** if `bytes` is not `dst.len() * T::SIZE` bytes long.
+///
+/// ```
+/// use asprim::{decode_slice, Endian};
+///
+/// // 16-bit big-endian samples
+/// let bytes = [0x00, 0x01, 0xff, 0xfe, 0x80, 0x00];
+/// let mut samples = [0i16; 3];
+/// decode_slice(&bytes, Endian::Big, &mut samples);
+/// assert_eq!(samples, [1, -2, i16::MIN]);
+/// ```
+pub fn decode_slice<T: PrimBytes>(bytes: &[u8], endian: Endian, dst: &mut [T]) {
+    assert_eq!(bytes.len(), dst.len() * T::SIZE, "decode_slice: lengths do not match");
+    for (d, b) in dst.iter_mut().zip(bytes.chunks_exact(T::SIZE)) {
+        *d = T::read_bytes(b, endian);
+    }
+}
+
+/// Write the values of `src` tightly packed, in the byte order `endian`,
+/// into `bytes`. This is the inverse of `decode_slice`.
+///
+/// ***Panics*** if `bytes` is not `src.len() * T::SIZE` bytes long.
+///
+/// ```
+/// use asprim::{encode_slice, Endian};
+///
+/// let mut bytes = [0; 4];
+/// encode_slice(&[1u16, 0x0203], Endian::Little, &mut bytes);
+/// assert_eq!(bytes, [1, 0, 3, 2]);
+/// ```
+pub fn encode_slice<T: PrimBytes>(src: &[T], endian: Endian, bytes: &mut [u8]) {
+    assert_eq!(bytes.len(), src.len() * T::SIZE, "encode_slice: lengths do not match");
+    for (&s, b) in src.iter().zip(bytes.chunks_exact_mut(T::SIZE)) {
+        s.write_bytes(b, endian);
+    }
+}
+
+/// Read the tightly packed values of `bytes`, in the byte order `endian`,
+/// into a new vector.
+///
+/// Requires the `alloc` feature.
+///
+/// ***Panics*** if the length of `bytes` is not a multiple of `T::SIZE`.
+///
+/// ```
+/// use asprim::{decode_vec, Endian};
+///
+/// let values: Vec<f64> = decode_vec(&1.5f64.to_le_bytes(), Endian::Little);
+/// assert_eq!(values, [1.5]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn decode_vec<T: PrimBytes>(bytes: &[u8], endian: Endian) -> Vec<T> {
+    assert_eq!(bytes.len() % T::SIZE, 0, "decode_vec: length is not a multiple of the size");
+    bytes.chunks_exact(T::SIZE).map(|b| T::read_bytes(b, endian)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        fn check<T: PrimBytes>() {
+            let mut bytes = [0; 64];
+            for &endian in &[Endian::Little, Endian::Big, Endian::NATIVE] {
+                for &x in T::BOUNDARY_VALUES {
+                    let bytes = &mut bytes[..T::SIZE];
+                    x.write_bytes(bytes, endian);
+                    let y = T::read_bytes(bytes, endian);
+                    assert!(y == x || y.as_f64().is_nan());
+                }
+            }
+        }
+        check::<u8>();
+        check::<i16>();
+        check::<u32>();
+        check::<i64>();
+        #[cfg(feature = "i128")]
+        check::<u128>();
+        check::<isize>();
+        check::<f32>();
+        check::<f64>();
+    }
+
+    #[test]
+    fn slices() {
+        let mut bytes = [0; 12];
+        encode_slice(&[1.0f32, -2., 0.5], Endian::Big, &mut bytes);
+        assert_eq!(bytes[..4], [0x3f, 0x80, 0, 0]);
+        let mut xs = [0f32; 3];
+        decode_slice(&bytes, Endian::Big, &mut xs);
+        assert_eq!(xs, [1., -2., 0.5]);
+        let mut words = [0u32; 3];
+        decode_slice(&bytes, Endian::Little, &mut words);
+        assert_eq!(words[0], 0x803f);
+    }
+
+    #[test]
+    fn wrappers() {
+        let x = Be::new(0x1234u16);
+        assert_eq!(x.0, 0x1234u16.to_be());
+        assert_eq!(x.get(), 0x1234);
+        assert_eq!(Le::new(-2i64).0, (-2i64).to_le() as u64);
+        assert_eq!(Be::new(1f32).0, 1f32.to_bits().to_be());
+        // Bits that are a signaling NaN when swapped
+        assert_eq!(Be::new(f32::from_bits(0xa07f)).get().to_bits(), 0xa07f);
+        assert_eq!(Le::new(f32::from_bits(0xa07f)).get().to_bits(), 0xa07f);
+        assert_eq!(Be::<f64>::cast_from(-0.5f32).get(), -0.5);
+        assert!(Be::<f32>::checked_cast_from(f64::NAN).unwrap().get().is_nan());
+        assert_eq!(Le::<i8>::saturating_cast_from(1000), Le::new(127));
+        assert!(Be::new(1u32) < Be::new(256));
+        assert!(Be::new(f32::NAN) != Be::new(f32::NAN));
+        let mut y = Le::<u32>::default();
+        y.set(7);
+        assert_eq!(y.as_u8(), 7);
+        assert_eq!(<Be<u32> as AsPrim>::KIND, PrimKind::U32);
+    }
+
+    #[test]
+    #[cfg(feature = "test-util")]
+    fn wrappers_conform() {
+        use test_util::verify_asprim_impl;
+
+        fn check<T: PrimBytes>() {
+            for &x in T::BOUNDARY_VALUES {
+                verify_asprim_impl(&[Be::new(x)]);
+                verify_asprim_impl(&[Le::new(x)]);
+            }
+        }
+        check::<u8>();
+        check::<i16>();
+        check::<u32>();
+        check::<i64>();
+        #[cfg(feature = "i128")]
+        check::<i128>();
+        check::<usize>();
+        check::<f32>();
+        check::<f64>();
+    }
+
+    #[test]
+    fn wrapper_layout() {
+        fn check<T: PrimBytes>() {
+            assert!(T::SIZE <= 16);
+            assert_eq!(<T::Raw as PrimBytes>::SIZE, T::SIZE);
+            assert_eq!(mem::size_of::<Be<T>>(), mem::size_of::<T>());
+            assert_eq!(mem::align_of::<Be<T>>(), mem::align_of::<T>());
+            assert_eq!(mem::size_of::<Le<T>>(), mem::size_of::<T>());
+            assert_eq!(mem::align_of::<Le<T>>(), mem::align_of::<T>());
+        }
+        check::<u8>();
+        check::<i8>();
+        check::<u16>();
+        check::<i16>();
+        check::<u32>();
+        check::<i32>();
+        check::<u64>();
+        check::<i64>();
+        #[cfg(feature = "i128")]
+        check::<u128>();
+        #[cfg(feature = "i128")]
+        check::<i128>();
+        check::<usize>();
+        check::<isize>();
+        check::<f32>();
+        check::<f64>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_mismatch() {
+        decode_slice(&[0; 7], Endian::Little, &mut [0u32; 2]);
+    }
+}
@@ -0,0 +1,58 @@
+use core::cell::Cell;
+
+use AsPrim;
+
+/// Get and set the value of a `Cell` with a cast, using `as`.
+///
+/// ```
+/// use std::cell::Cell;
+/// use asprim::{AsPrim, CellAs};
+///
+/// let ticks = Cell::new(0u32);
+/// ticks.set_from(1.9e3f64);
+/// assert_eq!(ticks.get_as::<f32>(), 1900.);
+///
+/// // Saturate first for a clamping store
+/// ticks.set_from((-5i64).saturating_as::<u32>());
+/// assert_eq!(ticks.get(), 0);
+/// ```
+pub trait CellAs {
+    /// The primitive type of the value.
+    type Prim: AsPrim;
+
+    /// Get the value and cast it to `U`.
+    fn get_as<U: AsPrim>(&self) -> U;
+
+    /// Cast `x` to the primitive type and set it.
+    fn set_from<T: AsPrim>(&self, x: T);
+}
+
+impl<P: AsPrim> CellAs for Cell<P> {
+    type Prim = P;
+
+    #[inline]
+    fn get_as<U: AsPrim>(&self) -> U {
+        self.get().as_()
+    }
+
+    #[inline]
+    fn set_from<T: AsPrim>(&self, x: T) {
+        self.set(x.as_())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_set() {
+        let c = Cell::new(-1i8);
+        assert_eq!(c.get_as::<u8>(), 255);
+        c.set_from(300u16);
+        assert_eq!(c.get(), 44);
+        let f = Cell::new(0f32);
+        f.set_from(u8::MAX);
+        assert_eq!(f.get_as::<i64>(), 255);
+    }
+}
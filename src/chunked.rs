@@ -0,0 +1,109 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use {AsPrim, CastPolicy};
+
+/// A converter that accepts input in pieces of any length and emits it
+/// cast to the type `U` in chunks of exactly `N` elements.
+///
+/// Elements that do not yet fill a chunk are kept in an internal buffer
+/// until the next call to `push`. It does not allocate.
+///
+/// ```
+/// use asprim::ChunkedCaster;
+///
+/// let mut caster = ChunkedCaster::<u16, f32, 4>::new();
+/// let mut chunks = Vec::new();
+/// caster.push(&[1, 2, 3], |chunk| chunks.push(*chunk));
+/// caster.push(&[4, 5, 6, 7, 8, 9], |chunk| chunks.push(*chunk));
+/// assert_eq!(chunks, [[1., 2., 3., 4.], [5., 6., 7., 8.]]);
+/// assert_eq!(caster.pending(), [9.]);
+/// ```
+pub struct ChunkedCaster<T, U, const N: usize> {
+    buf: [U; N],
+    len: usize,
+    policy: CastPolicy,
+    src: PhantomData<fn(T)>,
+}
+
+impl<T: AsPrim, U: AsPrim, const N: usize> ChunkedCaster<T, U, N> {
+    /// Create a converter that casts using `as`.
+    ///
+    /// ***Panics*** if `N` is zero.
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_policy(CastPolicy::As)
+    }
+
+    /// Create a converter that casts using `policy`.
+    ///
+    /// ***Panics*** if `N` is zero.
+    #[inline]
+    pub fn with_policy(policy: CastPolicy) -> Self {
+        assert!(N > 0, "ChunkedCaster: chunk size must be nonzero");
+        ChunkedCaster { buf: [U::default(); N], len: 0, policy, src: PhantomData }
+    }
+
+    /// Cast the elements of `src` into the buffer, and call `emit` with
+    /// each chunk as it is filled.
+    pub fn push<F>(&mut self, src: &[T], mut emit: F)
+        where F: FnMut(&[U; N])
+    {
+        for &x in src {
+            self.buf[self.len] = self.policy.cast(x);
+            self.len += 1;
+            if self.len == N {
+                emit(&self.buf);
+                self.len = 0;
+            }
+        }
+    }
+
+    /// The elements that do not yet fill a chunk.
+    #[inline]
+    pub fn pending(&self) -> &[U] {
+        &self.buf[..self.len]
+    }
+
+    /// Discard the pending elements.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<T: AsPrim, U: AsPrim, const N: usize> Default for ChunkedCaster<T, U, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, U: fmt::Debug, const N: usize> fmt::Debug for ChunkedCaster<T, U, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ChunkedCaster")
+            .field("pending", &&self.buf[..self.len])
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks() {
+        let mut caster = ChunkedCaster::<i32, u8, 2>::with_policy(CastPolicy::Saturating);
+        let mut n = 0;
+        caster.push(&[-1, 300, 7], |chunk| {
+            assert_eq!(*chunk, [0, 255]);
+            n += 1;
+        });
+        assert_eq!(n, 1);
+        assert_eq!(caster.pending(), [7]);
+        caster.clear();
+        assert!(caster.pending().is_empty());
+        caster.push(&[], |_| unreachable!());
+    }
+}
@@ -0,0 +1,133 @@
+use PrimKind;
+
+/// How a cast between two primitive types can change values, as computed by
+/// `cast_class`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CastClass {
+    /// Every value is cast exactly.
+    Lossless,
+    /// Integer to float, or `f64` to `f32`: the value may be rounded to the
+    /// closest float. `f64` to `f32`, and `u128` to `f32`, can also overflow
+    /// to infinity.
+    MayLosePrecision,
+    /// Float to integer: the fractional part is dropped, and the value may
+    /// be out of range.
+    MayTruncate,
+    /// Integer to integer: the value may be out of range. Saturating casts
+    /// clamp it, `as` wraps it.
+    MaySaturate,
+}
+
+/// Classify the cast from `from` to `to`.
+///
+/// The classes of `usize` and `isize` hold for every pointer width from 16
+/// to 64 bits, not only for the target that the code is compiled for, so
+/// that a conversion plan checked on one target holds on the others: a
+/// cast from them is classified as from 64 bits, and a cast to them as to
+/// 16 bits.
+///
+/// ```
+/// use asprim::{cast_class, CastClass, PrimKind};
+///
+/// const CLASS: CastClass = cast_class(PrimKind::U16, PrimKind::F32);
+/// assert_eq!(CLASS, CastClass::Lossless);
+/// assert_eq!(cast_class(PrimKind::U32, PrimKind::F32), CastClass::MayLosePrecision);
+/// assert_eq!(cast_class(PrimKind::F32, PrimKind::U32), CastClass::MayTruncate);
+/// assert_eq!(cast_class(PrimKind::I8, PrimKind::U64), CastClass::MaySaturate);
+///
+/// assert_eq!(cast_class(PrimKind::Usize, PrimKind::U64), CastClass::Lossless);
+/// assert_eq!(cast_class(PrimKind::U32, PrimKind::Usize), CastClass::MaySaturate);
+/// assert_eq!(cast_class(PrimKind::Usize, PrimKind::F64), CastClass::MayLosePrecision);
+/// ```
+pub const fn cast_class(from: PrimKind, to: PrimKind) -> CastClass {
+    if from as u8 == to as u8 {
+        return CastClass::Lossless;
+    }
+    match (from.is_float(), to.is_float()) {
+        (false, false) => {
+            let (from_bits, to_bits) = (max_bits(from), min_bits(to));
+            let fits = if from.is_signed() == to.is_signed() {
+                from_bits <= to_bits
+            } else {
+                !from.is_signed() && from_bits < to_bits
+            };
+            if fits { CastClass::Lossless } else { CastClass::MaySaturate }
+        }
+        (false, true) => {
+            let digits = if from.is_signed() { max_bits(from) - 1 } else { max_bits(from) };
+            if digits <= to.mantissa_digits() {
+                CastClass::Lossless
+            } else {
+                CastClass::MayLosePrecision
+            }
+        }
+        (true, false) => CastClass::MayTruncate,
+        (true, true) => {
+            if from.bits() <= to.bits() {
+                CastClass::Lossless
+            } else {
+                CastClass::MayLosePrecision
+            }
+        }
+    }
+}
+
+/// The most bits that `kind` has on any pointer width.
+const fn max_bits(kind: PrimKind) -> u32 {
+    match kind {
+        PrimKind::Usize | PrimKind::Isize => 64,
+        _ => kind.bits(),
+    }
+}
+
+/// The fewest bits that `kind` has on any pointer width.
+const fn min_bits(kind: PrimKind) -> u32 {
+    match kind {
+        PrimKind::Usize | PrimKind::Isize => 16,
+        _ => kind.bits(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use {AsPrim, PrimValue};
+
+    /// Check the classes against casts of the boundary values.
+    #[test]
+    fn boundary_values() {
+        for &from in PrimKind::ALL {
+            for &to in PrimKind::ALL {
+                let class = cast_class(from, to);
+                for &x in f64::BOUNDARY_VALUES.iter().chain(&[0.5, -1.5, 1e3]) {
+                    let x = PrimValue::from(x).cast_to(from);
+                    let y = x.cast_to(to);
+                    let exact = y.cast_to(from) == x || x.as_::<f64>().is_nan();
+                    if class == CastClass::Lossless {
+                        assert!(exact, "{:?} to {:?}: {:?} became {:?}", from, to, x, y);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pointer_sized() {
+        use self::CastClass::*;
+        use PrimKind::*;
+
+        assert_eq!(cast_class(Usize, Usize), Lossless);
+        assert_eq!(cast_class(Isize, Isize), Lossless);
+        assert_eq!(cast_class(Usize, Isize), MaySaturate);
+        assert_eq!(cast_class(U16, Usize), Lossless);
+        assert_eq!(cast_class(U8, Isize), Lossless);
+        assert_eq!(cast_class(I16, Isize), Lossless);
+        assert_eq!(cast_class(U16, Isize), MaySaturate);
+        assert_eq!(cast_class(U64, Usize), MaySaturate);
+        assert_eq!(cast_class(Isize, I64), Lossless);
+        assert_eq!(cast_class(Usize, I64), MaySaturate);
+        assert_eq!(cast_class(Isize, F64), MayLosePrecision);
+        assert_eq!(cast_class(Usize, F32), MayLosePrecision);
+        assert_eq!(cast_class(F32, Usize), MayTruncate);
+    }
+}
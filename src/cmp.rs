@@ -0,0 +1,408 @@
+use core::cmp::Ordering;
+
+use {is_negative, AsPrim, Signed, Unsigned};
+
+#[inline]
+pub(crate) fn abs(x: f64) -> f64 {
+    // Clear the sign bit, so that the absolute value of -0.0 is 0.0
+    f64::from_bits(x.to_bits() & !(1 << 63))
+}
+
+/// An integer as its sign and magnitude; zero is never negative.
+#[derive(Copy, Clone, PartialEq, Eq)]
+struct Wide {
+    negative: bool,
+    magnitude: Unsigned,
+}
+
+impl Wide {
+    #[inline]
+    fn of<T: AsPrim>(x: T) -> Self {
+        if is_negative(x) {
+            Wide { negative: true, magnitude: x.as_::<Signed>().unsigned_abs() }
+        } else {
+            Wide { negative: false, magnitude: x.as_() }
+        }
+    }
+
+    #[inline]
+    fn cmp(self, other: Wide) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+
+    /// The distance between `self` and `other`, or `None` if it overflows.
+    #[inline]
+    fn abs_diff(self, other: Wide) -> Option<Unsigned> {
+        if self.negative == other.negative {
+            Some(if self.magnitude > other.magnitude {
+                self.magnitude - other.magnitude
+            } else {
+                other.magnitude - self.magnitude
+            })
+        } else {
+            self.magnitude.checked_add(other.magnitude)
+        }
+    }
+}
+
+/// Compare the integer `x` to the finite float `y`.
+fn cmp_int_float(x: Wide, y: f64) -> Ordering {
+    let y_abs = abs(y);
+    if y_abs >= Unsigned::MAX.as_f64() {
+        // Beyond the range of every integer type
+        return if y < 0. { Ordering::Greater } else { Ordering::Less };
+    }
+    let magnitude = y_abs.as_::<Unsigned>();
+    let trunc = Wide { negative: y < 0. && magnitude != 0, magnitude };
+    x.cmp(trunc).then(if y_abs == magnitude.as_f64() {
+        Ordering::Equal
+    } else if y < 0. {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    })
+}
+
+/// Compare `a` and `b` by their exact values, or return `None` if either
+/// is NaN.
+///
+/// Unlike comparing them after a cast to a common type, this never rounds
+/// or wraps.
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use asprim::cmp_exact;
+///
+/// assert_eq!(cmp_exact(u64::MAX, u64::MAX as f64), Some(Ordering::Less));
+/// assert_eq!(cmp_exact(-1i32, u32::MAX), Some(Ordering::Less));
+/// assert_eq!(cmp_exact(0.5f32, 0u8), Some(Ordering::Greater));
+/// assert_eq!(cmp_exact(f64::NAN, 0u8), None);
+/// ```
+pub fn cmp_exact<A: AsPrim, B: AsPrim>(a: A, b: B) -> Option<Ordering> {
+    match (A::KIND.is_float(), B::KIND.is_float()) {
+        (false, false) => Some(Wide::of(a).cmp(Wide::of(b))),
+        (true, true) => a.as_f64().partial_cmp(&b.as_f64()),
+        (false, true) => {
+            let b = b.as_f64();
+            if b.is_nan() {
+                None
+            } else {
+                Some(cmp_int_float(Wide::of(a), b))
+            }
+        }
+        (true, false) => cmp_exact(b, a).map(Ordering::reverse),
+    }
+}
+
+/// Return `true` if `b` has the exact value of `a`; NaN is exact as NaN.
+#[inline]
+pub(crate) fn is_exact<A: AsPrim, B: AsPrim>(a: A, b: B) -> bool {
+    match cmp_exact(a, b) {
+        Some(ord) => ord == Ordering::Equal,
+        None => a.as_f64().is_nan() && b.as_f64().is_nan(),
+    }
+}
+
+/// Return `true` if `T::checked_cast_from(x)` succeeds, for a primitive
+/// `T`, without counting the cast in `metrics`.
+pub(crate) fn fits<T: AsPrim, A: AsPrim>(x: A) -> bool {
+    if T::KIND.is_float() {
+        // Only a finite value that overflows to infinity is out of range
+        return x.as_f64().is_infinite() || !T::cast_from(x).as_f64().is_infinite();
+    }
+    if !A::KIND.is_float() {
+        return is_exact(x, T::cast_from(x));
+    }
+    // Like `checked_value!`: the bounds are powers of two, and a float
+    // `x` is exact in f64
+    let pow2 = |e: u32| f64::from_bits((1023 + e as u64) << 52);
+    let bits = T::KIND.bits();
+    let (min, max_plus_one) = if T::KIND.is_signed() {
+        (-pow2(bits - 1), pow2(bits - 1))
+    } else {
+        (0., pow2(bits))
+    };
+    let x = x.as_f64();
+    (x > min - 1. || x >= min) && x < max_plus_one
+}
+
+/// Cast `x` to the primitive type `T` like `checked_as`, for the range
+/// checks inside the crate's own casts, which `metrics` does not count.
+#[inline]
+pub(crate) fn checked<T: AsPrim, A: AsPrim>(x: A) -> Option<T> {
+    if fits::<T, A>(x) { Some(T::cast_from(x)) } else { None }
+}
+
+/// The key of `x` in the IEEE 754 total order.
+#[inline]
+fn total_order_key(x: f64) -> i64 {
+    let bits = x.to_bits() as i64;
+    // Flip all but the sign bit of negative values, so that their order
+    // reverses; the sign bit then sorts them before the positive values
+    bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+/// Compare `a` and `b` by their exact values, using the IEEE 754 total
+/// order for floats.
+///
+/// NaN with the sign bit set is less, and other NaN greater, than every
+/// number, and `-0.0` is less than `0.0` and integer zero.
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use asprim::total_cmp_as;
+///
+/// assert_eq!(total_cmp_as(f64::NAN, u64::MAX), Ordering::Greater);
+/// assert_eq!(total_cmp_as(-0.0f32, 0u8), Ordering::Less);
+/// assert_eq!(total_cmp_as(7i8, 7.5f32), Ordering::Less);
+///
+/// let mut column = [3.5, f64::NAN, -1., 2.];
+/// column.sort_by(|&a, &b| total_cmp_as(a, b));
+/// assert_eq!(column[..3], [-1., 2., 3.5]);
+/// ```
+pub fn total_cmp_as<A: AsPrim, B: AsPrim>(a: A, b: B) -> Ordering {
+    if A::KIND.is_float() && B::KIND.is_float() {
+        return total_order_key(a.as_f64()).cmp(&total_order_key(b.as_f64()));
+    }
+    if B::KIND.is_float() {
+        return total_cmp_as(b, a).reverse();
+    }
+    // Now only `a` may be a float
+    let x = a.as_f64();
+    if x.is_nan() {
+        return if x.is_sign_negative() { Ordering::Less } else { Ordering::Greater };
+    }
+    match cmp_exact(a, b) {
+        Some(Ordering::Equal) if x.is_sign_negative() && x == 0. => Ordering::Less,
+        Some(ord) => ord,
+        None => unreachable!(),
+    }
+}
+
+/// Return the smaller of `a` and `b`, compared exactly, cast to the type
+/// `R` using `as`.
+///
+/// If they are equal, return `a`. If one is NaN, return the other.
+///
+/// ```
+/// use asprim::min_as;
+///
+/// assert_eq!(min_as::<i64, _, _>(u64::MAX, -1i32), -1);
+/// assert_eq!(min_as::<f64, _, _>(f32::NAN, 3u8), 3.);
+/// ```
+pub fn min_as<R: AsPrim, A: AsPrim, B: AsPrim>(a: A, b: B) -> R {
+    match cmp_exact(a, b) {
+        Some(Ordering::Greater) => b.as_(),
+        None if a.as_f64().is_nan() => b.as_(),
+        _ => a.as_(),
+    }
+}
+
+/// Return the larger of `a` and `b`, compared exactly, cast to the type
+/// `R` using `as`.
+///
+/// If they are equal, return `a`. If one is NaN, return the other.
+///
+/// ```
+/// use asprim::max_as;
+///
+/// assert_eq!(max_as::<u64, _, _>(u64::MAX, 1e19f64), u64::MAX);
+/// assert_eq!(max_as::<i32, _, _>(-7i8, -7.5f32), -7);
+/// ```
+pub fn max_as<R: AsPrim, A: AsPrim, B: AsPrim>(a: A, b: B) -> R {
+    match cmp_exact(a, b) {
+        Some(Ordering::Less) => b.as_(),
+        None if a.as_f64().is_nan() => b.as_(),
+        _ => a.as_(),
+    }
+}
+
+/// Return `true` if `a` and `b` are equal within a tolerance, comparing
+/// them as `f64`.
+///
+/// Like Python's `math.isclose`, they are close if
+/// `|a - b| <= max(rel_tol * max(|a|, |b|), abs_tol)`. Use `rel_tol` to
+/// allow for the relative error of floats, and `abs_tol` for comparisons
+/// near zero, where any relative tolerance is too strict. NaN is not close
+/// to anything, and infinities are only close to themselves.
+///
+/// ```
+/// use asprim::approx_eq;
+///
+/// assert!(approx_eq(1000u32, 1000.0004f64, 0., 1e-6));
+/// assert!(!approx_eq(1000u32, 1000.01f64, 0., 1e-6));
+/// assert!(approx_eq(0u8, 1e-12f64, 1e-9, 0.));
+/// ```
+pub fn approx_eq<P: AsPrim, Q: AsPrim>(a: P, b: Q, abs_tol: f64, rel_tol: f64) -> bool {
+    let (a, b) = (a.as_f64(), b.as_f64());
+    if a == b {
+        return true;
+    }
+    if a.is_infinite() || b.is_infinite() {
+        return false;
+    }
+    let tol = rel_tol * if abs(a) > abs(b) { abs(a) } else { abs(b) };
+    abs(a - b) <= if tol > abs_tol { tol } else { abs_tol }
+}
+
+/// Return the distance `|a - b|`, cast to the type `R` using `as`.
+///
+/// For integers, it is computed exactly, so it does not overflow for any
+/// inputs: the result is the exact distance cast with `as`, even when it
+/// is beyond the widest integer type. If either is a float, it is computed
+/// in `f64`.
+///
+/// ```
+/// use asprim::abs_diff_as;
+///
+/// assert_eq!(abs_diff_as::<u8, _, _>(3u8, 10u8), 7);
+/// assert_eq!(abs_diff_as::<u64, _, _>(i64::MIN, i64::MAX), u64::MAX);
+/// assert_eq!(abs_diff_as::<f64, _, _>(0.5f32, -2i8), 2.5);
+/// ```
+pub fn abs_diff_as<R: AsPrim, A: AsPrim, B: AsPrim>(a: A, b: B) -> R {
+    if A::KIND.is_float() || B::KIND.is_float() {
+        return abs(a.as_f64() - b.as_f64()).as_();
+    }
+    let (a, b) = (Wide::of(a), Wide::of(b));
+    match a.abs_diff(b) {
+        Some(d) => d.as_(),
+        // Only the widest types overflow: the signs differ, and the sum of
+        // the magnitudes needs one more bit. `as` to an integer keeps its
+        // low bits, which the wrapping sum has too
+        None if !R::KIND.is_float() => a.magnitude.wrapping_add(b.magnitude).as_(),
+        None => {
+            // The sum is 2h + low; rounding h with low as a sticky bit,
+            // far below its last mantissa bit, then doubling, rounds once
+            let (x, y) = (a.magnitude, b.magnitude);
+            let h = (x >> 1) + (y >> 1) + (x & y & 1);
+            let low = (x ^ y) & 1;
+            R::cast_from(R::cast_from(h | low).as_f64() * 2.)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact() {
+        use core::cmp::Ordering::*;
+
+        assert_eq!(cmp_exact(u32::MAX, -1i64), Some(Greater));
+        assert_eq!(cmp_exact(i64::MIN, i64::MIN as f64), Some(Equal));
+        assert_eq!(cmp_exact(-1i8, -0.5f64), Some(Less));
+        assert_eq!(cmp_exact(-1i8, -1.5f64), Some(Greater));
+        assert_eq!(cmp_exact(0u8, -0.0f32), Some(Equal));
+        assert_eq!(cmp_exact(0u8, -0.5f32), Some(Greater));
+        assert_eq!(cmp_exact((1u64 << 53) + 1, (1u64 << 53) as f64), Some(Greater));
+        assert_eq!(cmp_exact(u64::MAX, f32::INFINITY), Some(Less));
+        assert_eq!(cmp_exact(f64::NEG_INFINITY, i8::MIN), Some(Less));
+        assert_eq!(cmp_exact(1e300f64, u64::MAX), Some(Greater));
+        assert_eq!(cmp_exact(0.1f32, 0.1f64), Some(Greater));
+        assert_eq!(cmp_exact(3u16, f32::NAN), None);
+        #[cfg(feature = "i128")]
+        assert_eq!(cmp_exact(u128::MAX, i128::MIN), Some(Greater));
+    }
+
+    #[test]
+    fn total() {
+        use core::cmp::Ordering::*;
+
+        let neg_nan = -f64::NAN;
+        assert_eq!(total_cmp_as(neg_nan, f64::NEG_INFINITY), Less);
+        assert_eq!(total_cmp_as(neg_nan, i64::MIN), Less);
+        assert_eq!(total_cmp_as(i64::MIN, -f32::NAN), Greater);
+        assert_eq!(total_cmp_as(f32::NAN, f64::NAN), Equal);
+        assert_eq!(total_cmp_as(-0.0f64, 0.0f32), Less);
+        assert_eq!(total_cmp_as(0.0f64, 0u8), Equal);
+        assert_eq!(total_cmp_as(0u8, -0.0f64), Greater);
+        assert_eq!(total_cmp_as(u64::MAX, u64::MAX as f64), Less);
+        assert_eq!(total_cmp_as(-2i8, -1.5f64), Less);
+        assert_eq!(total_cmp_as(f32::INFINITY, f64::INFINITY), Equal);
+        assert_eq!(total_cmp_as(3u8, -7i32), Greater);
+    }
+
+    #[test]
+    fn abs_diff() {
+        assert_eq!(abs_diff_as::<u32, _, _>(0u32, u32::MAX), u32::MAX);
+        assert_eq!(abs_diff_as::<i8, _, _>(-3i64, -10i8), 7);
+        assert_eq!(abs_diff_as::<f64, _, _>(u64::MAX, i64::MIN), 27670116110564327424.);
+        assert!(abs_diff_as::<f32, _, _>(f32::NAN, 0).is_nan());
+        #[cfg(feature = "i128")]
+        assert_eq!(abs_diff_as::<f64, _, _>(u128::MAX, -1i8), 2f64.powi(128));
+        assert_eq!(abs_diff_as::<f64, _, _>(-0.0f64, 0.0f32).to_bits(), 0);
+
+        // The distances beyond the widest type, without `i128` those of
+        // u64::MAX and i64::MIN
+        assert_eq!(abs_diff_as::<Unsigned, _, _>(Unsigned::MAX, Signed::MIN), Signed::MAX as Unsigned);
+        assert_eq!(abs_diff_as::<u8, _, _>(Unsigned::MAX, Signed::MIN), 255);
+        assert_eq!(abs_diff_as::<f64, _, _>(Unsigned::MAX, Signed::MIN), 1.5 * Unsigned::MAX.as_f64());
+        // 2^w + 2^(w - 53) + 1 is just above the midpoint of two f64 values,
+        // which rounding twice would miss
+        let w = 8 * core::mem::size_of::<Unsigned>() as i32;
+        let y = (1 << (w - 1)) + (1 << (w - 53)) + 1 as Unsigned;
+        assert_eq!(abs_diff_as::<f64, _, _>(Signed::MIN, y), 2f64.powi(w) * (1. + f64::EPSILON));
+    }
+
+    #[test]
+    fn fits_like_checked() {
+        fn check<A: AsPrim, T: AsPrim>() {
+            for &x in A::BOUNDARY_VALUES {
+                let y = T::checked_cast_from(x);
+                assert_eq!(fits::<T, A>(x), y.is_some(), "{:?} as {}", x, T::NAME);
+                assert!(checked::<T, A>(x) == y || y.map_or(false, |y| y.as_f64().is_nan()));
+            }
+        }
+        fn check_to<T: AsPrim>() {
+            check::<u8, T>();
+            check::<i16, T>();
+            check::<u32, T>();
+            check::<i64, T>();
+            check::<Unsigned, T>();
+            check::<Signed, T>();
+            check::<isize, T>();
+            check::<f32, T>();
+            check::<f64, T>();
+        }
+        check_to::<u8>();
+        check_to::<i8>();
+        check_to::<u16>();
+        check_to::<i32>();
+        check_to::<u64>();
+        check_to::<i64>();
+        check_to::<Unsigned>();
+        check_to::<Signed>();
+        check_to::<usize>();
+        check_to::<f32>();
+        check_to::<f64>();
+    }
+
+    #[test]
+    fn min_max() {
+        assert_eq!(min_as::<i32, _, _>(4000000000u32, 7i32), 7);
+        assert!(max_as::<f32, _, _>(f64::NAN, f64::NAN).is_nan());
+        assert_eq!(max_as::<u8, _, _>(1u8, 1.0f32), 1);
+    }
+
+    #[test]
+    fn non_finite() {
+        assert!(approx_eq(f64::INFINITY, f32::INFINITY, 0., 0.));
+        assert!(!approx_eq(f64::INFINITY, f64::MAX, 1., 1.));
+        assert!(!approx_eq(f64::NAN, f64::NAN, 1., 1.));
+        assert!(!approx_eq(0, f32::NAN, 1., 1.));
+    }
+
+    #[test]
+    fn tolerances() {
+        assert!(approx_eq(-5i8, -5.0001f64, 1e-3, 0.));
+        assert!(approx_eq(-5i8, -5.0001f64, 0., 1e-4));
+        assert!(!approx_eq(-5i8, -5.0001f64, 1e-5, 1e-5));
+        assert!(approx_eq(u64::MAX, 18446744073709551616f64, 0., 0.));
+    }
+}
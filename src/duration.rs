@@ -0,0 +1,166 @@
+//! Conversions between numbers of seconds, milliseconds or nanoseconds and
+//! `Duration`.
+//!
+//! Requires the `duration` feature.
+
+use core::time::Duration;
+
+use cmp::checked;
+use {AsPrim, Unsigned};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// Convert `x` units of `unit` nanoseconds to a duration, or return `None`
+/// if it is negative, NaN or too large.
+fn to_duration<T: AsPrim>(x: T, unit: u32) -> Option<Duration> {
+    if T::KIND.is_float() {
+        let secs = x.as_f64() * f64::from(unit) / f64::from(NANOS_PER_SEC);
+        // Also false for NaN
+        if !(0. ..18446744073709551616.).contains(&secs) {
+            return None;
+        }
+        let whole = secs.as_u64();
+        let nanos = ((secs - whole.as_f64()) * f64::from(NANOS_PER_SEC)).as_u32();
+        Some(Duration::new(whole, nanos.min(NANOS_PER_SEC - 1)))
+    } else {
+        let x = checked::<Unsigned, _>(x)?;
+        let per_sec = Unsigned::from(NANOS_PER_SEC / unit);
+        let secs = checked::<u64, _>(x / per_sec)?;
+        Some(Duration::new(secs, (x % per_sec).as_u32() * unit))
+    }
+}
+
+fn saturating_to_duration<T: AsPrim>(x: T, unit: u32) -> Duration {
+    to_duration(x, unit).unwrap_or_else(|| if x > T::default() {
+        Duration::new(u64::MAX, NANOS_PER_SEC - 1)
+    } else {
+        Duration::new(0, 0)
+    })
+}
+
+/// Convert `d` to a number of units of `unit` nanoseconds, as an integer
+/// (truncated) or a float.
+fn from_duration<T: AsPrim>(d: Duration, unit: u32) -> Option<T> {
+    if T::KIND.is_float() {
+        let units = d.as_secs().as_f64() * f64::from(NANOS_PER_SEC / unit)
+            + f64::from(d.subsec_nanos()) / f64::from(unit);
+        units.checked_as()
+    } else {
+        Unsigned::from(d.as_secs())
+            .checked_mul(Unsigned::from(NANOS_PER_SEC / unit))?
+            .checked_add(Unsigned::from(d.subsec_nanos() / unit))?
+            .checked_as()
+    }
+}
+
+fn saturating_from_duration<T: AsPrim>(d: Duration, unit: u32) -> T {
+    from_duration(d, unit).unwrap_or_else(|| Unsigned::MAX.saturating_as())
+}
+
+macro_rules! as_duration_methods {
+    ($($method:ident $checked:ident $unit:expr, $name:expr;)*) => {
+        $(
+            #[doc = "Convert self as a number of "]
+            #[doc = $name]
+            #[doc = " to a `Duration`, clamping negative values and NaN \
+                     to zero and values that are too large to the \
+                     largest duration."]
+            #[inline]
+            fn $method(self) -> Duration {
+                saturating_to_duration(self, $unit)
+            }
+            #[doc = "Convert self as a number of "]
+            #[doc = $name]
+            #[doc = " to a `Duration`, or return `None` if it is negative, \
+                     NaN or too large."]
+            #[inline]
+            fn $checked(self) -> Option<Duration> {
+                to_duration(self, $unit)
+            }
+        )*
+    }
+}
+
+/// Convert numbers of seconds, milliseconds or nanoseconds to `Duration`.
+///
+/// ```
+/// use std::time::Duration;
+/// use asprim::AsDuration;
+///
+/// assert_eq!(1.5f64.as_duration_secs(), Duration::from_millis(1500));
+/// assert_eq!(1500u16.as_duration_millis(), Duration::from_millis(1500));
+/// assert_eq!((-1i32).as_duration_nanos(), Duration::from_secs(0));
+/// assert_eq!((-1i32).checked_as_duration_nanos(), None);
+/// ```
+pub trait AsDuration : AsPrim {
+    as_duration_methods!{
+        as_duration_secs checked_as_duration_secs NANOS_PER_SEC, "seconds";
+        as_duration_millis checked_as_duration_millis 1_000_000, "milliseconds";
+        as_duration_micros checked_as_duration_micros 1_000, "microseconds";
+        as_duration_nanos checked_as_duration_nanos 1, "nanoseconds";
+    }
+}
+
+impl<T: AsPrim> AsDuration for T { }
+
+macro_rules! duration_as_functions {
+    ($($function:ident $checked:ident $unit:expr, $name:expr;)*) => {
+        $(
+            #[doc = "Convert `d` to a number of "]
+            #[doc = $name]
+            #[doc = " of the type `T`, clamping it to the range of `T`. \
+                     Integers are truncated."]
+            #[inline]
+            pub fn $function<T: AsPrim>(d: Duration) -> T {
+                saturating_from_duration(d, $unit)
+            }
+            #[doc = "Convert `d` to a number of "]
+            #[doc = $name]
+            #[doc = " of the type `T`, or return `None` if it is out of \
+                     range for `T`. Integers are truncated."]
+            #[inline]
+            pub fn $checked<T: AsPrim>(d: Duration) -> Option<T> {
+                from_duration(d, $unit)
+            }
+        )*
+    }
+}
+
+duration_as_functions!{
+    duration_as_secs checked_duration_as_secs NANOS_PER_SEC, "seconds";
+    duration_as_millis checked_duration_as_millis 1_000_000, "milliseconds";
+    duration_as_micros checked_duration_as_micros 1_000, "microseconds";
+    duration_as_nanos checked_duration_as_nanos 1, "nanoseconds";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to() {
+        let max = Duration::new(u64::MAX, NANOS_PER_SEC - 1);
+        assert_eq!(0.25f32.as_duration_millis(), Duration::from_micros(250));
+        assert_eq!(f64::NAN.as_duration_secs(), Duration::new(0, 0));
+        assert_eq!(f64::INFINITY.as_duration_secs(), max);
+        assert_eq!(f64::INFINITY.checked_as_duration_secs(), None);
+        assert_eq!(u64::MAX.as_duration_secs(), Duration::new(u64::MAX, 0));
+        assert_eq!(1_000_000_007u32.as_duration_nanos(), Duration::new(1, 7));
+        #[cfg(feature = "i128")]
+        assert_eq!(u128::MAX.checked_as_duration_millis(), None);
+    }
+
+    #[test]
+    fn from() {
+        let d = Duration::new(3, 500_000_000);
+        assert_eq!(duration_as_secs::<f64>(d), 3.5);
+        assert_eq!(duration_as_secs::<u8>(d), 3);
+        assert_eq!(duration_as_millis::<i32>(d), 3500);
+        assert_eq!(duration_as_nanos::<u32>(d), 3_500_000_000);
+        assert_eq!(duration_as_nanos::<i32>(d), i32::MAX);
+        assert_eq!(checked_duration_as_nanos::<i32>(d), None);
+        assert_eq!(duration_as_micros::<f32>(Duration::from_micros(7)), 7.);
+        assert_eq!(duration_as_nanos::<Unsigned>(Duration::new(u64::MAX, 0)),
+                   Unsigned::from(u64::MAX).saturating_mul(1_000_000_000));
+    }
+}
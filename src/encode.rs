@@ -0,0 +1,188 @@
+//! A compact binary encoding of `PrimValue`.
+
+use core::fmt;
+
+use bytes::{Endian, PrimBytes};
+use cmp::checked;
+use {PrimKind, PrimValue};
+
+/// The error of `PrimValue::decode`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeError {
+    /// The buffer ends before the value does.
+    Truncated,
+    /// The kind tag is not one of a type this build supports.
+    UnknownKind(u8),
+    /// The value does not fit the target type, like a `usize` wider than
+    /// the target's pointer width.
+    OutOfRange,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecodeError::Truncated => f.write_str("encoded value is truncated"),
+            DecodeError::UnknownKind(tag) => write!(f, "unknown kind tag {}", tag),
+            DecodeError::OutOfRange => f.write_str("encoded value is out of range for the target type"),
+        }
+    }
+}
+
+/// The tag of each kind in the encoding. It does not depend on the
+/// features, so that the encoding is always the same.
+const TAGS: &[(PrimKind, u8)] = &[
+    (PrimKind::U8, 0),
+    (PrimKind::I8, 1),
+    (PrimKind::U16, 2),
+    (PrimKind::I16, 3),
+    (PrimKind::U32, 4),
+    (PrimKind::I32, 5),
+    (PrimKind::U64, 6),
+    (PrimKind::I64, 7),
+    #[cfg(feature = "i128")]
+    (PrimKind::U128, 8),
+    #[cfg(feature = "i128")]
+    (PrimKind::I128, 9),
+    (PrimKind::Usize, 10),
+    (PrimKind::Isize, 11),
+    (PrimKind::F32, 12),
+    (PrimKind::F64, 13),
+];
+
+impl PrimValue {
+    /// The most bytes that `encode` writes, for a 128-bit value.
+    pub const MAX_ENCODED_LEN: usize = 17;
+
+    /// The number of bytes that `encode` writes for this value.
+    #[inline]
+    pub fn encoded_len(self) -> usize {
+        1 + match self.kind() {
+            PrimKind::Usize | PrimKind::Isize => 8,
+            kind => kind.bits() as usize / 8,
+        }
+    }
+
+    /// Write the value to the start of `buf` as a one byte kind tag followed
+    /// by the value in little-endian byte order, and return the number of
+    /// bytes written.
+    ///
+    /// `usize` and `isize` are written as 64-bit values, so that the
+    /// encoding does not depend on the target.
+    ///
+    /// ***Panics*** if `buf` is shorter than `encoded_len()`.
+    ///
+    /// ```
+    /// use asprim::PrimValue;
+    ///
+    /// let mut buf = [0; PrimValue::MAX_ENCODED_LEN];
+    /// let n = PrimValue::I16(-2).encode(&mut buf);
+    /// assert_eq!(buf[..n], [3, 0xfe, 0xff]);
+    /// assert_eq!(PrimValue::decode(&buf[..n]), Ok((PrimValue::I16(-2), n)));
+    /// ```
+    pub fn encode(self, buf: &mut [u8]) -> usize {
+        let len = self.encoded_len();
+        assert!(buf.len() >= len, "PrimValue::encode: buffer is too short");
+        let kind = self.kind();
+        buf[0] = TAGS.iter().find(|&&(k, _)| k == kind).unwrap().1;
+        let payload = &mut buf[1..len];
+        match self {
+            PrimValue::U8(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::I8(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::U16(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::I16(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::U32(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::I32(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::U64(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::I64(x) => x.write_bytes(payload, Endian::Little),
+            #[cfg(feature = "i128")]
+            PrimValue::U128(x) => x.write_bytes(payload, Endian::Little),
+            #[cfg(feature = "i128")]
+            PrimValue::I128(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::Usize(x) => (x as u64).write_bytes(payload, Endian::Little),
+            PrimValue::Isize(x) => (x as i64).write_bytes(payload, Endian::Little),
+            PrimValue::F32(x) => x.write_bytes(payload, Endian::Little),
+            PrimValue::F64(x) => x.write_bytes(payload, Endian::Little),
+        }
+        len
+    }
+
+    /// Read a value written by `encode` from the start of `buf`, and return
+    /// it with the number of bytes read.
+    ///
+    /// ```
+    /// use asprim::{DecodeError, PrimValue};
+    ///
+    /// let frame = [12, 0, 0, 0x80, 0x3f, 0, 7];
+    /// let (value, n) = PrimValue::decode(&frame).unwrap();
+    /// assert_eq!(value, PrimValue::F32(1.));
+    /// assert_eq!(PrimValue::decode(&frame[n..]), Ok((PrimValue::U8(7), 2)));
+    /// assert_eq!(PrimValue::decode(&frame[..3]), Err(DecodeError::Truncated));
+    /// assert_eq!(PrimValue::decode(&[200]), Err(DecodeError::UnknownKind(200)));
+    /// ```
+    pub fn decode(buf: &[u8]) -> Result<(PrimValue, usize), DecodeError> {
+        let tag = *buf.first().ok_or(DecodeError::Truncated)?;
+        let kind = match TAGS.iter().find(|&&(_, t)| t == tag) {
+            Some(&(kind, _)) => kind,
+            None => return Err(DecodeError::UnknownKind(tag)),
+        };
+        let len = PrimValue::U8(0).cast_to(kind).encoded_len();
+        if buf.len() < len {
+            return Err(DecodeError::Truncated);
+        }
+        let payload = &buf[1..len];
+        fn read<T: PrimBytes>(payload: &[u8]) -> T {
+            T::read_bytes(payload, Endian::Little)
+        }
+        let value = match kind {
+            PrimKind::U8 => PrimValue::U8(read(payload)),
+            PrimKind::I8 => PrimValue::I8(read(payload)),
+            PrimKind::U16 => PrimValue::U16(read(payload)),
+            PrimKind::I16 => PrimValue::I16(read(payload)),
+            PrimKind::U32 => PrimValue::U32(read(payload)),
+            PrimKind::I32 => PrimValue::I32(read(payload)),
+            PrimKind::U64 => PrimValue::U64(read(payload)),
+            PrimKind::I64 => PrimValue::I64(read(payload)),
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => PrimValue::U128(read(payload)),
+            #[cfg(feature = "i128")]
+            PrimKind::I128 => PrimValue::I128(read(payload)),
+            PrimKind::Usize => {
+                let x: u64 = read(payload);
+                PrimValue::Usize(checked(x).ok_or(DecodeError::OutOfRange)?)
+            }
+            PrimKind::Isize => {
+                let x: i64 = read(payload);
+                PrimValue::Isize(checked(x).ok_or(DecodeError::OutOfRange)?)
+            }
+            PrimKind::F32 => PrimValue::F32(read(payload)),
+            PrimKind::F64 => PrimValue::F64(read(payload)),
+        };
+        Ok((value, len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buf = [0; PrimValue::MAX_ENCODED_LEN];
+        for &kind in PrimKind::ALL {
+            let value = PrimValue::from(-1i8).saturating_cast_to(kind);
+            let n = value.encode(&mut buf);
+            assert_eq!(n, value.encoded_len());
+            assert!(n <= PrimValue::MAX_ENCODED_LEN);
+            assert_eq!(PrimValue::decode(&buf[..n]), Ok((value, n)));
+            assert_eq!(PrimValue::decode(&buf[..n - 1]), Err(DecodeError::Truncated));
+        }
+        assert_eq!(PrimValue::decode(&[]), Err(DecodeError::Truncated));
+        assert_eq!(PrimValue::Usize(1).encode(&mut buf), 9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_buffer() {
+        PrimValue::U32(1).encode(&mut [0; 4]);
+    }
+}
@@ -0,0 +1,89 @@
+//! The sign, mantissa and exponent of floats, for `PrimFloat::decompose`
+//! and `PrimFloat::recompose`.
+
+/// Split the `width` bits of a float with `digits` significant bits and
+/// the maximum exponent `max_exp` into sign, integer mantissa and binary
+/// exponent.
+#[inline]
+pub(crate) fn decompose(bits: u64, width: u32, digits: u32, max_exp: i32) -> (bool, u64, i32) {
+    let frac_bits = digits - 1;
+    let bias = max_exp - 1;
+    let negative = bits >> (width - 1) != 0;
+    let fraction = bits & ((1 << frac_bits) - 1);
+    let exp_field = ((bits >> frac_bits) & ((1 << (width - digits)) - 1)) as i32;
+    if exp_field == 0 {
+        (negative, fraction, 1 - bias - frac_bits as i32)
+    } else {
+        (negative, fraction | 1 << frac_bits, exp_field - bias - frac_bits as i32)
+    }
+}
+
+/// Join a sign, integer mantissa and binary exponent into the bits of a
+/// float of the layout of `decompose`, or return `None` if the value is
+/// not exactly representable.
+#[inline]
+pub(crate) fn recompose(negative: bool, mantissa: u64, exponent: i32, width: u32, digits: u32,
+                        max_exp: i32) -> Option<u64>
+{
+    let sign = (negative as u64) << (width - 1);
+    if mantissa == 0 {
+        return Some(sign);
+    }
+    let frac_bits = digits - 1;
+    let bias = max_exp - 1;
+    let len = 64 - mantissa.leading_zeros() as i32;
+    // The exponent of the leading bit, and of the last bit of the result
+    let lead = exponent as i64 + len as i64 - 1;
+    if lead > bias as i64 {
+        return None;
+    }
+    let min_normal = 1 - bias as i64;
+    let last = if lead >= min_normal {
+        lead - frac_bits as i64
+    } else {
+        min_normal - frac_bits as i64
+    };
+    let shift = last - exponent as i64;
+    let m = if shift <= 0 {
+        // `-shift` is less than `frac_bits`, so the result fits
+        mantissa << -shift
+    } else if shift < 64 && mantissa & ((1 << shift) - 1) == 0 {
+        mantissa >> shift
+    } else {
+        return None;
+    };
+    let exp_field = if lead >= min_normal { (lead + bias as i64) as u64 } else { 0 };
+    Some(sign | exp_field << frac_bits | (m & ((1 << frac_bits) - 1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use PrimFloat;
+
+    #[test]
+    fn round_trip() {
+        fn check<F: PrimFloat>() {
+            for &x in F::BOUNDARY_VALUES {
+                if !x.as_f64().is_finite() {
+                    continue;
+                }
+                let (negative, mantissa, exponent) = x.decompose();
+                assert!(mantissa >> F::KIND.mantissa_digits() == 0);
+                let y = F::recompose(negative, mantissa, exponent).unwrap();
+                assert!(y == x && y.as_f64().is_sign_negative() == negative);
+                // The same value, unnormalized
+                if mantissa != 0 && mantissa.leading_zeros() > 2 {
+                    assert_eq!(F::recompose(negative, mantissa << 2, exponent - 2), Some(x));
+                }
+            }
+        }
+        check::<f32>();
+        check::<f64>();
+        assert_eq!(f64::recompose(false, u64::MAX, 0), None);
+        assert_eq!(f64::recompose(false, 1 << 63, 960), Some(2f64.powi(1023)));
+        assert_eq!(f64::recompose(false, 1 << 63, 961), None);
+        assert_eq!(f64::recompose(false, 1 << 10, -1084), Some(f64::from_bits(1)));
+        assert_eq!(f32::recompose(false, 3, -150), None);
+        assert_eq!(f32::INFINITY.decompose(), (false, 1 << 23, 105));
+    }
+}
@@ -0,0 +1,96 @@
+//! Fast formatting of primitive values with `itoa` and `ryu`.
+//!
+//! Requires the `itoa` and `ryu` features.
+
+use core::fmt;
+
+use {AsPrim, PrimKind};
+
+/// A buffer to format primitive values in, without allocating.
+///
+/// Integers are formatted like `Display` does, and floats in the shortest
+/// form that parses back to the same value, like `ryu` does: `1.0`, `1e20`,
+/// `NaN`, `inf`.
+///
+/// ```
+/// use asprim::PrimBuffer;
+///
+/// let mut buf = PrimBuffer::new();
+/// assert_eq!(buf.format(-128i8), "-128");
+/// assert_eq!(buf.format(0.3f32), "0.3");
+/// assert_eq!(buf.format(1e20f64), "1e20");
+/// ```
+#[derive(Copy, Clone, Default)]
+pub struct PrimBuffer {
+    int: itoa::Buffer,
+    float: ryu::Buffer,
+}
+
+impl PrimBuffer {
+    /// Create a buffer.
+    #[inline]
+    pub fn new() -> Self {
+        PrimBuffer { int: itoa::Buffer::new(), float: ryu::Buffer::new() }
+    }
+
+    /// Format `x` in the buffer and return it as a string.
+    pub fn format<P: AsPrim>(&mut self, x: P) -> &str {
+        match P::KIND {
+            PrimKind::U8 => self.int.format(x.as_u8()),
+            PrimKind::I8 => self.int.format(x.as_i8()),
+            PrimKind::U16 => self.int.format(x.as_u16()),
+            PrimKind::I16 => self.int.format(x.as_i16()),
+            PrimKind::U32 => self.int.format(x.as_u32()),
+            PrimKind::I32 => self.int.format(x.as_i32()),
+            PrimKind::U64 => self.int.format(x.as_u64()),
+            PrimKind::I64 => self.int.format(x.as_i64()),
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => self.int.format(x.as_u128()),
+            #[cfg(feature = "i128")]
+            PrimKind::I128 => self.int.format(x.as_i128()),
+            PrimKind::Usize => self.int.format(x.as_usize()),
+            PrimKind::Isize => self.int.format(x.as_isize()),
+            PrimKind::F32 => self.float.format(x.as_f32()),
+            PrimKind::F64 => self.float.format(x.as_f64()),
+        }
+    }
+}
+
+impl fmt::Debug for PrimBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("PrimBuffer")
+    }
+}
+
+/// Write `x` to `w`, formatted like `PrimBuffer::format` does.
+///
+/// ```
+/// use asprim::format_prim;
+///
+/// let mut line = String::new();
+/// format_prim(42u16, &mut line).unwrap();
+/// line.push(',');
+/// format_prim(2.5f64, &mut line).unwrap();
+/// assert_eq!(line, "42,2.5");
+/// ```
+#[inline]
+pub fn format_prim<P: AsPrim, W: fmt::Write + ?Sized>(x: P, w: &mut W) -> fmt::Result {
+    w.write_str(PrimBuffer::new().format(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats() {
+        let mut buf = PrimBuffer::new();
+        assert_eq!(buf.format(u64::MAX), "18446744073709551615");
+        assert_eq!(buf.format(-1isize), "-1");
+        assert_eq!(buf.format(1.0f64), "1.0");
+        assert_eq!(buf.format(f32::NAN), "NaN");
+        assert_eq!(buf.format(f64::NEG_INFINITY), "-inf");
+        #[cfg(feature = "i128")]
+        assert_eq!(buf.format(i128::MIN), "-170141183460469231731687303715884105728");
+    }
+}
@@ -0,0 +1,52 @@
+//! Traits combining `AsPrim` with the `funty` traits.
+
+use funty::{Floating, Integral};
+
+use {PrimFloat, PrimInt};
+
+/// A primitive integer type, for both `asprim` and `funty`.
+///
+/// This is `PrimInt + funty::Integral` as a single bound, and it is
+/// implemented for every type that implements both.
+///
+/// `funty::Fundamental` has methods `as_u8` and so on, like `AsPrim`, so
+/// calling those on a generic value is ambiguous; use `as_` or
+/// `AsPrim::as_u8(x)` instead.
+///
+/// ```
+/// use asprim::{AsPrim, IntegralPrim};
+///
+/// fn mean<T: IntegralPrim>(xs: &[T]) -> f64 {
+///     let sum: T = xs.iter().copied().sum();
+///     sum.as_::<f64>() / xs.len() as f64
+/// }
+///
+/// assert_eq!(mean(&[1u8, 2, 6]), 3.);
+/// ```
+pub trait IntegralPrim : PrimInt + Integral { }
+
+impl<T> IntegralPrim for T where T: PrimInt + Integral { }
+
+/// A primitive floating point type, for both `asprim` and `funty`.
+///
+/// This is `PrimFloat + funty::Floating` as a single bound, and it is
+/// implemented for every type that implements both.
+pub trait FloatingPrim : PrimFloat + Floating { }
+
+impl<T> FloatingPrim for T where T: PrimFloat + Floating { }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scale<T: IntegralPrim, F: FloatingPrim>(x: T, f: F) -> T {
+        (f * x.as_::<F>()).saturating_as()
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(scale(100u8, 1.5f32), 150);
+        assert_eq!(scale(100u8, 3.0f64), 255);
+        assert_eq!(scale(-100i64, 0.5f64), -50);
+    }
+}
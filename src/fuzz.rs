@@ -0,0 +1,77 @@
+//! `arbitrary` implementations, for fuzzing.
+//!
+//! Requires the `arbitrary` feature.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use {AsPrim, CastPolicy, NonFinite, PrimKind, PrimValue};
+
+impl<'a> Arbitrary<'a> for PrimKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(PrimKind::ALL)?)
+    }
+}
+
+/// Pick one of `T::BOUNDARY_VALUES` a quarter of the time.
+fn prim<'a, T: AsPrim + Arbitrary<'a>>(u: &mut Unstructured<'a>) -> Result<PrimValue>
+    where PrimValue: From<T>
+{
+    let x = if !T::BOUNDARY_VALUES.is_empty() && u.ratio(1, 4)? {
+        *u.choose(T::BOUNDARY_VALUES)?
+    } else {
+        u.arbitrary()?
+    };
+    Ok(PrimValue::from(x))
+}
+
+impl<'a> Arbitrary<'a> for PrimValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match u.arbitrary()? {
+            PrimKind::U8 => prim::<u8>(u),
+            PrimKind::I8 => prim::<i8>(u),
+            PrimKind::U16 => prim::<u16>(u),
+            PrimKind::I16 => prim::<i16>(u),
+            PrimKind::U32 => prim::<u32>(u),
+            PrimKind::I32 => prim::<i32>(u),
+            PrimKind::U64 => prim::<u64>(u),
+            PrimKind::I64 => prim::<i64>(u),
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => prim::<u128>(u),
+            #[cfg(feature = "i128")]
+            PrimKind::I128 => prim::<i128>(u),
+            PrimKind::Usize => prim::<usize>(u),
+            PrimKind::Isize => prim::<isize>(u),
+            PrimKind::F32 => prim::<f32>(u),
+            PrimKind::F64 => prim::<f64>(u),
+        }
+    }
+}
+
+impl<'a> Arbitrary<'a> for CastPolicy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[CastPolicy::As, CastPolicy::Saturating])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for NonFinite {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[NonFinite::Fail, NonFinite::Zero, NonFinite::Saturate])?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::Unstructured;
+    use {PrimKind, PrimValue};
+
+    #[test]
+    fn values() {
+        let data = [7u8; 256];
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let value: PrimValue = u.arbitrary().unwrap();
+            let kind: PrimKind = u.arbitrary().unwrap();
+            value.cast_to(kind);
+        }
+    }
+}
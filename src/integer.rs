@@ -0,0 +1,198 @@
+//! Integer-only accumulation and scaling, for targets without an FPU.
+
+use core::iter::FromIterator;
+
+use cmp::checked;
+use {AsPrim, PrimInt, Signed};
+
+/// Divide `n` by `d`, rounding half away from zero; `d` is positive.
+#[inline]
+fn div_round(n: Signed, d: Signed) -> Signed {
+    let (q, r) = (n / d, n % d);
+    let r_abs = if r < 0 { r.wrapping_neg() } else { r };
+    if r_abs >= d - r_abs {
+        if n < 0 { q - 1 } else { q + 1 }
+    } else {
+        q
+    }
+}
+
+/// A sum of integers of any primitive integer types, accumulated exactly
+/// in the widest signed integer type, `i128` (or `i64` without the `i128`
+/// feature), without using floats.
+///
+/// The sum and mean are only available while the total is in range; it
+/// remembers if an addition overflowed.
+///
+/// ```
+/// use asprim::IntSum;
+///
+/// let mut sum = IntSum::new();
+/// sum.push(u32::MAX);
+/// sum.push(-3i8);
+/// sum.push(2u8);
+/// assert_eq!(sum.count(), 3);
+/// assert_eq!(sum.sum::<u64>(), Some(u32::MAX as u64 - 1));
+/// assert_eq!(sum.sum::<u32>(), Some(u32::MAX - 1));
+/// assert_eq!(sum.sum::<i32>(), None);
+///
+/// let sum: IntSum = [1u16, 2, 2].iter().copied().collect();
+/// assert_eq!(sum.mean::<u16>(), Some(2));
+/// // The mean 1.666... in fixed point with 8 fractional bits
+/// assert_eq!(sum.mean_fixed::<u16>(8), Some(427));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntSum {
+    sum: Signed,
+    count: u64,
+    overflow: bool,
+}
+
+impl IntSum {
+    /// Create an empty sum.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `x` to the sum.
+    #[inline]
+    pub fn push<T: PrimInt>(&mut self, x: T) {
+        match checked::<Signed, _>(x).and_then(|x| self.sum.checked_add(x)) {
+            Some(sum) => self.sum = sum,
+            None => self.overflow = true,
+        }
+        self.count += 1;
+    }
+
+    /// The number of values added.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The sum of the values so far, or `None` if it overflowed or is out
+    /// of range for `T`.
+    #[inline]
+    pub fn sum<T: AsPrim>(&self) -> Option<T> {
+        if self.overflow { None } else { self.sum.checked_as() }
+    }
+
+    /// The mean of the values so far, rounded half away from zero, or
+    /// `None` if there are none, the sum overflowed, or the mean is out of
+    /// range for `T`.
+    #[inline]
+    pub fn mean<T: PrimInt>(&self) -> Option<T> {
+        self.mean_fixed(0)
+    }
+
+    /// The mean of the values so far, as a fixed-point number with
+    /// `frac_bits` fractional bits: the mean times 2^`frac_bits`, rounded
+    /// half away from zero.
+    ///
+    /// Return `None` where `mean` does, or if the scaled sum overflows.
+    pub fn mean_fixed<T: PrimInt>(&self, frac_bits: u32) -> Option<T> {
+        if self.overflow || self.count == 0 {
+            return None;
+        }
+        let scale = (1 as Signed).checked_shl(frac_bits).filter(|&s| s > 0)?;
+        let scaled = self.sum.checked_mul(scale)?;
+        div_round(scaled, checked(self.count)?).checked_as()
+    }
+}
+
+impl<T: PrimInt> Extend<T> for IntSum {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<T: PrimInt> FromIterator<T> for IntSum {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sum = Self::new();
+        sum.extend(iter);
+        sum
+    }
+}
+
+/// Map `x` linearly from the range `in_min` to `in_max` onto the range
+/// `out_min` to `out_max`, in integer arithmetic only, rounding half away
+/// from zero.
+///
+/// Values outside the input range map outside the output range. Either
+/// range may be reversed. Return `None` if `in_min` equals `in_max`, if the
+/// intermediate product overflows the widest integer type, or if the result
+/// is out of range for `U`.
+///
+/// ```
+/// use asprim::remap_int;
+///
+/// // A 12-bit ADC reading to millivolts of a 3.3 V reference
+/// assert_eq!(remap_int(2048u16, 0, 4095, 0u16, 3300), Some(1650));
+/// assert_eq!(remap_int(0i8, -100, 100, 0u8, 255), Some(128));
+/// assert_eq!(remap_int(10u8, 0, 10, 10i32, -10), Some(-10));
+/// assert_eq!(remap_int(1u8, 1, 1, 0u8, 1), None);
+/// ```
+pub fn remap_int<T, U>(x: T, in_min: T, in_max: T, out_min: U, out_max: U) -> Option<U>
+    where T: PrimInt, U: PrimInt
+{
+    let x = checked::<Signed, _>(x)?;
+    let (in_min, in_max) = (checked::<Signed, _>(in_min)?, checked::<Signed, _>(in_max)?);
+    let (out_min, out_max) = (checked::<Signed, _>(out_min)?, checked::<Signed, _>(out_max)?);
+    let mut num = x.checked_sub(in_min)?.checked_mul(out_max.checked_sub(out_min)?)?;
+    let mut den = in_max.checked_sub(in_min)?;
+    if den == 0 {
+        return None;
+    }
+    if den < 0 {
+        num = num.checked_neg()?;
+        den = den.checked_neg()?;
+    }
+    out_min.checked_add(div_round(num, den))?.checked_as()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounding() {
+        assert_eq!(div_round(5, 2), 3);
+        assert_eq!(div_round(-5, 2), -3);
+        assert_eq!(div_round(4, 3), 1);
+        assert_eq!(div_round(-4, 3), -1);
+        assert_eq!(div_round(Signed::MAX, Signed::MAX), 1);
+    }
+
+    #[test]
+    fn sums() {
+        let sum: IntSum = [i64::MAX, i64::MAX].iter().copied().collect();
+        #[cfg(feature = "i128")]
+        assert_eq!(sum.mean::<i64>(), Some(i64::MAX));
+        #[cfg(not(feature = "i128"))]
+        assert_eq!(sum.mean::<i64>(), None);
+        assert_eq!(IntSum::new().mean::<u8>(), None);
+        assert_eq!(IntSum::new().sum::<u8>(), Some(0));
+        let sum: IntSum = [-1i32, -2].iter().copied().collect();
+        assert_eq!(sum.mean::<i8>(), Some(-2));
+        assert_eq!(sum.mean::<u8>(), None);
+        assert_eq!(sum.sum::<f32>(), Some(-3.));
+        assert_eq!(sum.mean_fixed::<i16>(1), Some(-3));
+        assert_eq!(sum.mean_fixed::<i64>(200), None);
+    }
+
+    #[test]
+    fn remap() {
+        for x in 0..=255u8 {
+            let y = remap_int(x, 0, 255, 0u16, 1000).unwrap();
+            assert_eq!(y as u32, (x as u32 * 1000 + 127) / 255);
+        }
+        assert_eq!(remap_int(20u8, 0, 10, 0u8, 100), Some(200));
+        assert_eq!(remap_int(30u8, 0, 10, 0u8, 100), None);
+        #[cfg(feature = "i128")]
+        assert_eq!(remap_int(u32::MAX, 0, u32::MAX, i32::MIN, i32::MAX), Some(i32::MAX));
+        assert_eq!(remap_int(u64::MAX, 0, u64::MAX, i64::MIN, i64::MAX), None);
+    }
+}
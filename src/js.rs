@@ -0,0 +1,76 @@
+//! Conversions between JavaScript numbers and primitive types.
+//!
+//! Requires the `wasm-bindgen` feature.
+
+use core::convert::TryFrom;
+
+use wasm_bindgen::JsValue;
+
+use {AsPrim, Signed, Unsigned};
+
+/// The largest integer that JavaScript numbers hold exactly,
+/// `Number.MAX_SAFE_INTEGER`.
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.;
+
+#[inline]
+fn is_safe_integer(f: f64) -> bool {
+    (-MAX_SAFE_INTEGER..=MAX_SAFE_INTEGER).contains(&f)
+}
+
+/// Convert `x` to a JavaScript value.
+///
+/// Floats and safe integers, those of magnitude at most 2^53 - 1, become a
+/// `Number`, and other integers a `BigInt`, so they keep their exact value.
+///
+/// ```no_run
+/// use asprim::js::to_js_value;
+///
+/// assert_eq!(to_js_value(1u64 << 53).as_f64(), None);
+/// assert_eq!(to_js_value((1u64 << 53) - 1).as_f64(), Some(9007199254740991.));
+/// ```
+pub fn to_js_value<T: AsPrim>(x: T) -> JsValue {
+    if T::KIND.is_float() {
+        return JsValue::from_f64(x.as_f64());
+    }
+    match x.as_f64_exact() {
+        Some(f) if is_safe_integer(f) => JsValue::from_f64(f),
+        _ if x < T::default() => JsValue::from(x.as_::<Signed>()),
+        _ => JsValue::from(x.as_::<Unsigned>()),
+    }
+}
+
+/// Convert the JavaScript `Number` or `BigInt` `v` to the type `T`, or
+/// return `None` if it is not a number, or out of range, like
+/// `AsPrim::checked_as`.
+///
+/// Converting a `Number` to an integer type also fails if its magnitude is
+/// more than 2^53 - 1, since it may have been rounded already.
+///
+/// ```no_run
+/// extern crate asprim;
+/// extern crate wasm_bindgen;
+///
+/// use wasm_bindgen::JsValue;
+/// use asprim::js::from_js_value;
+///
+/// # fn main() {
+/// assert_eq!(from_js_value::<u8>(&JsValue::from_f64(255.)), Some(255));
+/// assert_eq!(from_js_value::<u64>(&JsValue::from_f64(2f64.powi(60))), None);
+/// assert_eq!(from_js_value::<u64>(&JsValue::from(1u64 << 60)), Some(1 << 60));
+/// # }
+/// ```
+pub fn from_js_value<T: AsPrim>(v: &JsValue) -> Option<T> {
+    if let Some(f) = v.as_f64() {
+        if !T::KIND.is_float() && !is_safe_integer(f) {
+            return None;
+        }
+        return f.checked_as();
+    }
+    if !v.is_bigint() {
+        return None;
+    }
+    match Signed::try_from(v.clone()) {
+        Ok(x) => x.checked_as(),
+        Err(_) => Unsigned::try_from(v.clone()).ok()?.checked_as(),
+    }
+}
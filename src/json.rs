@@ -0,0 +1,217 @@
+//! Conversions between `serde_json::Number` and primitive types.
+//!
+//! A JSON number holds an integer that fits `u64`, a negative integer that
+//! fits `i64`, or a finite `f64`. The conversions here keep integers as
+//! integers, so a 64-bit value never loses precision through `f64`, and
+//! `NumberPolicy` chooses how each of the three converts to the target.
+//!
+//! Requires the `serde_json` feature.
+//!
+//! ```
+//! extern crate asprim;
+//! extern crate serde_json;
+//!
+//! use serde_json::Number;
+//! use asprim::json::{from_number, from_number_with, to_number, NumberPolicy};
+//!
+//! # fn main() {
+//! let big = Number::from(u64::MAX - 1);
+//! assert_eq!(from_number::<u64>(&big), Some(u64::MAX - 1));
+//! assert_eq!(from_number::<i64>(&big), None);
+//! assert_eq!(to_number(u64::MAX - 1), Some(big));
+//!
+//! let half = Number::from_f64(2.5).unwrap();
+//! assert_eq!(from_number::<u8>(&half), Some(2));
+//! assert_eq!(from_number_with::<u8>(&half, NumberPolicy::STRICT), None);
+//! # }
+//! ```
+
+use serde_json::Number;
+
+use cmp::{checked, is_exact};
+use {AsPrim, CastPolicy, PrimValue, Signed, Unsigned};
+
+/// How to convert one kind of JSON number to the target type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NumberRule {
+    /// Fail, returning `None`.
+    Reject,
+    /// Fail unless the target holds the exact value.
+    Exact,
+    /// Fail if the value is out of range for the target, truncating floats
+    /// toward zero, like `AsPrim::checked_as`.
+    Checked,
+    /// Cast using the policy; this never fails.
+    Cast(CastPolicy),
+}
+
+impl NumberRule {
+    #[inline]
+    fn apply<S: AsPrim, T: AsPrim>(self, x: S) -> Option<T> {
+        match self {
+            NumberRule::Reject => None,
+            NumberRule::Exact => x.checked_as().filter(|&y| is_exact(x, y)),
+            NumberRule::Checked => x.checked_as(),
+            NumberRule::Cast(policy) => Some(policy.cast(x)),
+        }
+    }
+}
+
+/// The rules for converting each kind of JSON number, in `from_number_with`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NumberPolicy {
+    /// The rule for non-negative integers, which fit `u64`.
+    pub unsigned: NumberRule,
+    /// The rule for negative integers, which fit `i64`.
+    pub signed: NumberRule,
+    /// The rule for floats.
+    pub float: NumberRule,
+}
+
+impl NumberPolicy {
+    /// Convert every number only if the target holds its exact value.
+    pub const STRICT: NumberPolicy = NumberPolicy {
+        unsigned: NumberRule::Exact,
+        signed: NumberRule::Exact,
+        float: NumberRule::Exact,
+    };
+
+    /// Convert every number if it is in range, like `AsPrim::checked_as`.
+    /// This is the default.
+    pub const CHECKED: NumberPolicy = NumberPolicy {
+        unsigned: NumberRule::Checked,
+        signed: NumberRule::Checked,
+        float: NumberRule::Checked,
+    };
+
+    /// Clamp every number to the range of the target, like
+    /// `AsPrim::saturating_as`.
+    pub const SATURATING: NumberPolicy = NumberPolicy {
+        unsigned: NumberRule::Cast(CastPolicy::Saturating),
+        signed: NumberRule::Cast(CastPolicy::Saturating),
+        float: NumberRule::Cast(CastPolicy::Saturating),
+    };
+}
+
+impl Default for NumberPolicy {
+    fn default() -> Self {
+        NumberPolicy::CHECKED
+    }
+}
+
+/// Integers become `U64` if they are non-negative, else `I64`, and floats
+/// become `F64`.
+///
+/// With the `arbitrary_precision` feature of `serde_json`, numbers that
+/// are none of these become the nearest `F64`.
+impl<'a> From<&'a Number> for PrimValue {
+    fn from(n: &'a Number) -> Self {
+        if let Some(u) = n.as_u64() {
+            PrimValue::U64(u)
+        } else if let Some(i) = n.as_i64() {
+            PrimValue::I64(i)
+        } else {
+            PrimValue::F64(n.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+}
+
+/// Convert the JSON number `n` to the type `T`, or return `None` if it is
+/// out of range, like `AsPrim::checked_as`.
+///
+/// Floats are truncated toward zero when `T` is an integer.
+pub fn from_number<T: AsPrim>(n: &Number) -> Option<T> {
+    from_number_with(n, NumberPolicy::CHECKED)
+}
+
+/// Convert the JSON number `n` to the type `T` using the rule of `policy`
+/// for its kind, or return `None` if the rule fails.
+///
+/// ```
+/// extern crate asprim;
+/// extern crate serde_json;
+///
+/// use serde_json::Number;
+/// use asprim::json::{from_number_with, NumberPolicy, NumberRule};
+///
+/// # fn main() {
+/// // Integer fields: clamp integers, but refuse floats
+/// let policy = NumberPolicy {
+///     float: NumberRule::Reject,
+///     ..NumberPolicy::SATURATING
+/// };
+/// assert_eq!(from_number_with::<u8>(&Number::from(300), policy), Some(255));
+/// assert_eq!(from_number_with::<u8>(&Number::from(-1), policy), Some(0));
+/// assert_eq!(from_number_with::<u8>(&Number::from_f64(1.).unwrap(), policy), None);
+/// # }
+/// ```
+pub fn from_number_with<T: AsPrim>(n: &Number, policy: NumberPolicy) -> Option<T> {
+    match PrimValue::from(n) {
+        PrimValue::U64(u) => policy.unsigned.apply(u),
+        PrimValue::I64(i) => policy.signed.apply(i),
+        value => policy.float.apply(value.as_::<f64>()),
+    }
+}
+
+/// Convert `x` to a JSON number, or return `None` if it is a NaN or
+/// infinite float, or an integer out of the range of `i64` and `u64`.
+pub fn to_number<T: AsPrim>(x: T) -> Option<Number> {
+    value_to_number(PrimValue::of(x))
+}
+
+/// Convert `value` to a JSON number, like `to_number`.
+pub fn value_to_number(value: PrimValue) -> Option<Number> {
+    if value.kind().is_float() {
+        return Number::from_f64(value.as_());
+    }
+    let i = value.as_::<Signed>();
+    if value.kind().is_signed() && i < 0 {
+        checked::<i64, _>(i).map(Number::from)
+    } else {
+        checked::<u64, _>(value.as_::<Unsigned>()).map(Number::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_keep_precision() {
+        let n = Number::from(i64::MIN + 1);
+        assert_eq!(PrimValue::from(&n), PrimValue::I64(i64::MIN + 1));
+        assert_eq!(from_number::<i64>(&n), Some(i64::MIN + 1));
+        assert_eq!(from_number::<u64>(&n), None);
+        assert_eq!(from_number_with::<f64>(&n, NumberPolicy::STRICT), None);
+        assert_eq!(from_number_with::<f32>(&Number::from(1u64 << 40), NumberPolicy::STRICT),
+                   Some((1u64 << 40) as f32));
+        assert_eq!(from_number_with::<i8>(&n, NumberPolicy::SATURATING), Some(i8::MIN));
+        assert_eq!(from_number_with::<i8>(&Number::from(u64::MAX), NumberPolicy::default()),
+                   None);
+    }
+
+    #[test]
+    fn floats() {
+        let n = Number::from_f64(-0.5).unwrap();
+        assert_eq!(PrimValue::from(&n), PrimValue::F64(-0.5));
+        assert_eq!(from_number::<i32>(&n), Some(0));
+        assert_eq!(from_number::<f32>(&n), Some(-0.5));
+        assert_eq!(from_number_with::<f32>(&n, NumberPolicy::STRICT), Some(-0.5));
+        let tenth = Number::from_f64(0.1).unwrap();
+        assert_eq!(from_number_with::<f32>(&tenth, NumberPolicy::STRICT), None);
+        assert_eq!(from_number_with::<u8>(&Number::from_f64(1e10).unwrap(),
+                                          NumberPolicy::SATURATING), Some(255));
+    }
+
+    #[test]
+    fn to_json() {
+        assert_eq!(to_number(-3i8), Some(Number::from(-3)));
+        assert_eq!(to_number(0.25f32), Number::from_f64(0.25));
+        assert_eq!(to_number(f64::NAN), None);
+        #[cfg(feature = "i128")]
+        {
+            assert_eq!(to_number(u64::MAX as u128), Some(Number::from(u64::MAX)));
+            assert_eq!(to_number(i64::MIN as i128 - 1), None);
+        }
+    }
+}
@@ -1,6 +1,189 @@
 #![no_std]
 #![doc(html_root_url="https://docs.rs/asprim/0.2/")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "arbitrary-int")]
+extern crate arbitrary_int;
+#[cfg(feature = "funty")]
+extern crate funty;
+#[cfg(feature = "itoa")]
+extern crate itoa;
+#[cfg(feature = "libm")]
+extern crate libm;
+#[cfg(feature = "portable-atomic")]
+extern crate portable_atomic;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "pyo3")]
+extern crate pyo3;
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+#[cfg(feature = "rug")]
+extern crate rug;
+#[cfg(feature = "ryu")]
+extern crate ryu;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde_json")]
+extern crate serde_json;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "ufmt")]
+extern crate ufmt;
+#[cfg(feature = "wasm-bindgen")]
+extern crate wasm_bindgen;
+
+use core::cmp::Ordering;
+use core::fmt;
+
+pub use any_num::AnyNum;
+#[cfg(feature = "portable-atomic")]
+pub use atomic::AtomicAs;
+pub use bins::{bin_index, bin_index_f32};
+pub use bits::PrimBits;
+#[cfg(feature = "alloc")]
+pub use bytes::decode_vec;
+pub use bytes::{decode_slice, encode_slice, Be, Endian, Le, PrimBytes};
+pub use cell::CellAs;
+pub use chunked::ChunkedCaster;
+pub use class::{cast_class, CastClass};
+pub use cmp::{abs_diff_as, approx_eq, cmp_exact, max_as, min_as, total_cmp_as};
+pub use encode::DecodeError;
+#[cfg(all(feature = "itoa", feature = "ryu"))]
+pub use format::{format_prim, PrimBuffer};
+#[cfg(feature = "funty")]
+pub use funty_bridge::{FloatingPrim, IntegralPrim};
+#[cfg(feature = "duration")]
+pub use duration::{
+    checked_duration_as_micros,
+    checked_duration_as_millis,
+    checked_duration_as_nanos,
+    checked_duration_as_secs,
+    duration_as_micros,
+    duration_as_millis,
+    duration_as_nanos,
+    duration_as_secs,
+    AsDuration,
+};
+pub use integer::{remap_int, IntSum};
+pub use map_as::{OptionMapAs, ResultMapAs};
+pub use pipeline::Cast;
+pub use parse::{parse_prim, parse_prim_radix, ParsePrimError};
+pub use value::{CastError, ParseKindError, PrimKind, PrimValue};
+pub use view::{CastIter, CastView};
+pub use wrap::{Checked, Lossy};
+pub use round::RoundingMode;
+pub use si::format_si;
+pub use stats::{summarize, Summary, Summary32};
+pub use sum::CompensatedSum;
+pub use table::lookup_as;
+pub use slice::{
+    as_bits_slice,
+    cast_slice_into,
+    cast_slice_into_uninit,
+    cast_strided,
+    deinterleave_cast_into,
+    from_bits_slice,
+    interleave_cast_into,
+    saturating_cast_slice_into,
+    try_cast_slice_into,
+    BulkCastError,
+};
+#[cfg(feature = "rand")]
+pub use random::{random_prim, random_range_as};
+pub use reduce::{IteratorAs, ProductAs, SumAs};
+pub use report::{analyze, CastEntry, CastOutcome, CastReport};
+pub use range::{
+    cast_range,
+    cast_range_inclusive,
+    checked_cast_range,
+    checked_cast_range_inclusive,
+};
+
+#[macro_use]
+mod macros;
+#[cfg(feature = "arbitrary-int")]
+mod arbint;
+mod any_num;
+#[cfg(feature = "portable-atomic")]
+mod atomic;
+#[cfg(feature = "rug")]
+pub mod bignum;
+mod bins;
+mod bits;
+mod boundary;
+mod bytes;
+mod cell;
+mod chunked;
+mod class;
+mod cmp;
+#[cfg(feature = "duration")]
+mod duration;
+mod encode;
+mod float_parts;
+#[cfg(all(feature = "itoa", feature = "ryu"))]
+mod format;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "funty")]
+mod funty_bridge;
+#[cfg(feature = "wasm-bindgen")]
+pub mod js;
+mod integer;
+#[cfg(feature = "serde_json")]
+pub mod json;
+mod map_as;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "ufmt")]
+mod micro_fmt;
+#[cfg(feature = "numpy")]
+mod numpy;
+mod parse;
+mod pipeline;
+pub mod prelude;
+mod range;
+mod reduce;
+mod report;
+mod round;
+mod scaled;
+#[cfg(feature = "serde")]
+pub mod serde_prim;
+mod si;
+mod slice;
+mod stats;
+mod sum;
+mod table;
+#[cfg(feature = "pyo3")]
+pub mod python;
+#[cfg(feature = "quickcheck")]
+mod quick;
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+mod value;
+pub mod varint;
+mod view;
+mod wrap;
+
+macro_rules! from_methods {
+    ($($method:ident $t:ty),*) => {
+        $(
+            /// Cast `x` to `Self` using `as`, like `cast_from`.
+            #[inline(always)]
+            fn $method(x: $t) -> Self {
+                Self::cast_from(x)
+            }
+        )*
+    }
+}
+
 /// Cast to a primitive numeric type using `as`.
 ///
 /// `AsPrim` converts any primitive numeric type to any other,
@@ -26,14 +209,99 @@
 /// }
 /// ```
 ///
+/// # Implementing AsPrim
+///
+/// The trait is open: besides the primitive numeric types, it may be
+/// implemented for other numeric types that behave like one, for example
+/// an integer of unusual width. Use the `impl_as_prim!` macro to do so; the
+/// trait gains methods as the crate grows, and the macro is kept in step.
+///
+/// Implementations must keep the casts consistent with each other:
+///
+/// - `x.as_::<P>()` is the same as the method for `P`, like `x.as_u8()`,
+///   and likewise for the checked and saturating methods.
+/// - `x.checked_as::<P>()` is `Some(x.as_())` when the value is in range
+///   for `P`, and `None` otherwise; `x.saturating_as::<P>()` is equal to it
+///   whenever it is `Some`.
+/// - Casting to a type that holds the value exactly and back is the
+///   identity: if `x.as_::<P>().as_f64_exact()` is `Some`, so is
+///   `x.as_f64_exact()`, and the two are equal; `T::cast_from(x.as_::<P>())
+///   == x` whenever `x.checked_as::<P>()` is `Some` and `P` holds the
+///   value exactly.
+///
+/// The functions of this crate only rely on these rules and the trait's
+/// methods, never on the type being one of the built-in primitives.
+///
 /// # Rust Version
 ///
-/// Requires Rust 1.26 or later due to supporting as_u128
+/// Requires Rust 1.51 or later due to `ChunkedCaster` using const generics.
+///
+/// # Crate Features
 ///
-pub trait AsPrim : 'static + Copy {
+/// - `i128`: Enabled by default. Implement `AsPrim` for `u128` and `i128`,
+///   and add the methods casting to them. Disable it for targets where
+///   128-bit integers are a problem; generic code that does not use the
+///   128-bit methods compiles either way.
+/// - `alloc`: Add `decode_vec`, which needs the `alloc` crate.
+/// - `arbitrary`: Implement `arbitrary::Arbitrary` for `PrimKind`,
+///   `PrimValue`, `CastPolicy` and `NonFinite`, for fuzzing.
+/// - `arbitrary-int`: Implement `AsPrim` for the integer types of
+///   `arbitrary-int`, `u1` to `u127` and `i1` to `i127` (the types wider
+///   than 64 bits need `i128` too).
+/// - `duration`: Add `AsDuration` and the `duration_as_*` functions,
+///   converting numbers of seconds or smaller units to and from `Duration`.
+/// - `funty`: Add `IntegralPrim` and `FloatingPrim`, which combine
+///   `PrimInt` and `PrimFloat` with the `funty` traits in one bound.
+/// - `itoa` and `ryu`: Together, add `format_prim` and `PrimBuffer`, for
+///   fast formatting without allocating.
+/// - `libm`: Add the float rounding methods of `PrimFloat`, using `libm`,
+///   since `core` has no float rounding.
+/// - `metrics`: Add the `metrics` module, counting the saturating and
+///   checked casts that clamped or failed, by target type.
+/// - `numpy`: Add `PrimKind::to_numpy_typestr` and `from_numpy_typestr`,
+///   for the type strings of NumPy arrays and `.npy` files.
+/// - `portable-atomic`: Add `AtomicAs`, with `load_as` and `store_from`
+///   for the atomic types of `portable-atomic`, on any target.
+/// - `prefer-f32`: Compute the intermediate results of `Summary` and the
+///   float case of `bin_index` in `f32` instead of `f64`, for targets with
+///   only a single precision FPU. They are then less precise, and `f64`
+///   values outside the range of `f32` overflow; `bin_index` returns `None`
+///   when the bounds round to the same `f32` or overflow it. Cargo enables
+///   a feature for every crate in the build when any crate asks for it, so
+///   this changes the results for all users of `Summary` and `bin_index`
+///   in the program: only enable it in the final binary. Libraries should
+///   use `Summary32` and `bin_index_f32`, which compute in `f32` without
+///   the feature.
+/// - `proptest`: Add the `strategy` module, with `proptest` strategies for
+///   primitive values.
+/// - `pyo3`: Add the `python` module, converting Python numbers to
+///   `PrimValue` and other primitive types.
+/// - `quickcheck`: Implement `quickcheck::Arbitrary` for `PrimKind` and
+///   `PrimValue`.
+/// - `rand`: Add `random_prim` and `random_range_as`, to sample random
+///   values of any primitive type.
+/// - `rug`: Add the `bignum` module, converting `rug::Integer` and
+///   `rug::Float` to and from primitive types with explicit rounding.
+/// - `serde`: Add the `serde_prim` module, with functions to deserialize
+///   primitive fields from any number or numeric string.
+/// - `serde_json`: Add the `json` module, converting `serde_json::Number`
+///   to and from primitive types without losing 64-bit integers to `f64`.
+/// - `test-util`: Add the `test_util` module, to check implementations of
+///   `AsPrim` for other types.
+/// - `ufmt`: Implement `ufmt::uDisplay` and `ufmt::uDebug` for
+///   `PrimValue`, `PrimKind` and `CastError`, for targets where `core::fmt`
+///   is too large.
+/// - `wasm-bindgen`: Add the `js` module, converting JavaScript numbers
+///   and `BigInt`s to primitive types.
+///
+pub trait AsPrim : 'static + Copy + PartialEq + PartialOrd + Default + Send + Sync
+    + fmt::Debug + fmt::Display
+{
     fn as_usize(self) -> usize;
     fn as_isize(self) -> isize;
+    #[cfg(feature = "i128")]
     fn as_u128(self) -> u128;
+    #[cfg(feature = "i128")]
     fn as_i128(self) -> i128;
     fn as_u64(self) -> u64;
     fn as_i64(self) -> i64;
@@ -45,24 +313,487 @@ pub trait AsPrim : 'static + Copy {
     fn as_i8(self) -> i8;
     fn as_f32(self) -> f32;
     fn as_f64(self) -> f64;
+    fn as_usize_checked(self) -> Option<usize>;
+    fn as_isize_checked(self) -> Option<isize>;
+    #[cfg(feature = "i128")]
+    fn as_u128_checked(self) -> Option<u128>;
+    #[cfg(feature = "i128")]
+    fn as_i128_checked(self) -> Option<i128>;
+    fn as_u64_checked(self) -> Option<u64>;
+    fn as_i64_checked(self) -> Option<i64>;
+    fn as_u32_checked(self) -> Option<u32>;
+    fn as_i32_checked(self) -> Option<i32>;
+    fn as_u16_checked(self) -> Option<u16>;
+    fn as_i16_checked(self) -> Option<i16>;
+    fn as_u8_checked(self) -> Option<u8>;
+    fn as_i8_checked(self) -> Option<i8>;
+    fn as_f32_checked(self) -> Option<f32>;
+    fn as_f64_checked(self) -> Option<f64>;
+    fn as_usize_saturating(self) -> usize;
+    fn as_isize_saturating(self) -> isize;
+    #[cfg(feature = "i128")]
+    fn as_u128_saturating(self) -> u128;
+    #[cfg(feature = "i128")]
+    fn as_i128_saturating(self) -> i128;
+    fn as_u64_saturating(self) -> u64;
+    fn as_i64_saturating(self) -> i64;
+    fn as_u32_saturating(self) -> u32;
+    fn as_i32_saturating(self) -> i32;
+    fn as_u16_saturating(self) -> u16;
+    fn as_i16_saturating(self) -> i16;
+    fn as_u8_saturating(self) -> u8;
+    fn as_i8_saturating(self) -> i8;
+    fn as_f32_saturating(self) -> f32;
+    fn as_f64_saturating(self) -> f64;
+    /// Cast self to `f32`, or return `None` if the value can not be
+    /// represented exactly.
+    fn as_f32_exact(self) -> Option<f32>;
+    /// Cast self to `f64`, or return `None` if the value can not be
+    /// represented exactly.
+    fn as_f64_exact(self) -> Option<f64>;
     fn cast_from<T: AsPrim>(_: T) -> Self;
+    fn checked_cast_from<T: AsPrim>(_: T) -> Option<Self>;
+    fn saturating_cast_from<T: AsPrim>(_: T) -> Self;
+    /// The primitive type. For types implemented with `impl_as_prim!`, this
+    /// is the kind of the representation type.
+    const KIND: PrimKind;
+    /// The name of the primitive type, `KIND.name()`.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// fn describe<T: AsPrim>(_: T) -> &'static str {
+    ///     T::NAME
+    /// }
+    ///
+    /// assert_eq!(describe(1u32), "u32");
+    /// assert_eq!(describe(1.), "f64");
+    /// ```
+    const NAME: &'static str = Self::KIND.name();
+    /// Values to test casts with: zero, one, minus one if it exists, the
+    /// limits, and the values around the limits of exact integers in `f32`
+    /// (2^24) and `f64` (2^53) in range. Floats also include signed zero,
+    /// the integer limits of the 32- and 64-bit types, and the non-finite
+    /// values.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// for &x in u64::BOUNDARY_VALUES {
+    ///     if let Some(f) = x.as_f64_exact() {
+    ///         assert_eq!(f.as_u64(), x);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Only the primitive types are required to list their values. Types
+    /// made with `impl_as_prim!` list none unless they are given, and `Be`
+    /// and `Le` list none, since a constant of them can't be built for a
+    /// generic `T`; test them with the values of `T` wrapped by `Be::new`.
+    const BOUNDARY_VALUES: &'static [Self];
+    from_methods!{from_usize usize, from_isize isize,
+                  from_u64 u64, from_i64 i64, from_u32 u32, from_i32 i32,
+                  from_u16 u16, from_i16 i16, from_u8 u8, from_i8 i8,
+                  from_f32 f32, from_f64 f64}
+    #[cfg(feature = "i128")]
+    from_methods!{from_u128 u128, from_i128 i128}
     /// Cast self to the type `T`
     #[inline(always)]
     fn as_<T: AsPrim>(self) -> T {
         T::cast_from(self)
     }
+    /// Return `true` if self is not zero, like a C style boolean.
+    ///
+    /// NaN is not zero, so it is `true`; both zeros of a float are `false`.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// assert!(2u16.as_bool());
+    /// assert!(!(-0.0f32).as_bool());
+    /// assert!(f64::NAN.as_bool());
+    /// ```
+    #[inline]
+    fn as_bool(self) -> bool {
+        self != Self::cast_from(0)
+    }
+    /// Return `true` if self is zero; both zeros of a float are zero.
+    #[inline]
+    fn is_zero(self) -> bool {
+        !self.as_bool()
+    }
+    /// Return `true` if self is less than zero. This is never true for the
+    /// unsigned types, nor for `-0.0` and NaN.
+    ///
+    /// The floats have a deprecated `is_negative` method of their own, which
+    /// is `is_sign_negative` and takes precedence on a concrete float;
+    /// generic code always gets this one.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// fn below_zero<T: AsPrim>(x: T) -> bool {
+    ///     x.is_negative()
+    /// }
+    ///
+    /// assert!(below_zero(-3i8));
+    /// assert!(!below_zero(-0.0f64));
+    /// assert!(!below_zero(f32::NAN));
+    /// ```
+    #[inline]
+    fn is_negative(self) -> bool {
+        is_negative(self)
+    }
+    /// Return the sign of self: `-1` if it is negative, `1` if it is
+    /// greater than zero, and `0` for zero, `-0.0` and NaN.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// assert_eq!(u64::MAX.signum_i8(), 1);
+    /// assert_eq!((-0.5f32).signum_i8(), -1);
+    /// assert_eq!(f64::NAN.signum_i8(), 0);
+    /// ```
+    #[inline]
+    fn signum_i8(self) -> i8 {
+        let zero = Self::cast_from(0);
+        if self < zero { -1 } else if self > zero { 1 } else { 0 }
+    }
+    /// Return the absolute value of self.
+    ///
+    /// This does nothing for the unsigned types and clears the sign bit of
+    /// floats, NaN too. The absolute value of `MIN` of a signed type is out
+    /// of range, and it saturates to `MAX`.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// assert_eq!((-7i16).abs_prim(), 7);
+    /// assert_eq!(i8::MIN.abs_prim(), i8::MAX);
+    /// assert_eq!(200u8.abs_prim(), 200);
+    /// assert!((-0.0f64).abs_prim().is_sign_positive());
+    /// ```
+    #[inline]
+    fn abs_prim(self) -> Self {
+        if Self::KIND.is_float() {
+            let x = self.as_f64();
+            if x.is_sign_negative() { Self::cast_from(-x) } else { self }
+        } else if is_negative(self) {
+            let magnitude = self.as_::<Signed>().wrapping_neg() as Unsigned;
+            Self::saturating_cast_from(magnitude)
+        } else {
+            self
+        }
+    }
+    /// Cast self to the type `T`, or return `None` if the value is out of
+    /// range for `T`.
+    ///
+    /// Float to integer casts truncate toward zero like `as` does; NaN and
+    /// infinities are out of range. Casts to a float type only fail if a
+    /// finite value would overflow to infinity.
+    #[inline(always)]
+    fn checked_as<T: AsPrim>(self) -> Option<T> {
+        T::checked_cast_from(self)
+    }
+    /// Cast self to the type `T`, clamping out of range values to the
+    /// closest value of `T`.
+    ///
+    /// Float to integer casts truncate toward zero, and NaN becomes zero.
+    /// Casts to a float type clamp finite values to the finite range of the
+    /// target.
+    #[inline(always)]
+    fn saturating_as<T: AsPrim>(self) -> T {
+        T::saturating_cast_from(self)
+    }
+    /// Return the absolute error that `saturating_as::<T>` introduces, or
+    /// zero if it is exact.
+    ///
+    /// NaN cast to a float has no error; cast to an integer the error is
+    /// NaN.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// assert_eq!(300.cast_loss::<u8>(), 45.);
+    /// assert_eq!(2.75f64.cast_loss::<i32>(), 0.75);
+    /// assert_eq!(0.1f64.cast_loss::<f32>(), (0.1f32 as f64 - 0.1).abs());
+    /// assert_eq!(16777217.cast_loss::<f32>(), 1.);
+    /// ```
+    #[inline]
+    fn cast_loss<T: AsPrim>(self) -> f64 {
+        let y: T = self.saturating_as();
+        if cmp::cmp_exact(self, y) == Some(Ordering::Equal) {
+            0.
+        } else if self.as_f64().is_nan() {
+            if T::KIND.is_float() { 0. } else { f64::NAN }
+        } else {
+            cmp::abs_diff_as(self, y)
+        }
+    }
+    /// Cast self to the type `T` using `as`, and panic if the result does
+    /// not have the exact value of self.
+    ///
+    /// ***Panics*** with `msg`, the value, and the types, at the location
+    /// of the caller. NaN cast to a float is exact.
+    ///
+    /// ```
+    /// use asprim::AsPrim;
+    ///
+    /// let len = 300usize;
+    /// assert_eq!(len.expect_as::<u16>("length fits the header"), 300);
+    /// assert_eq!(0.5f64.expect_as::<f32>("half is exact"), 0.5);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use asprim::AsPrim;
+    ///
+    /// // panics with "length fits the header: 70000 (usize) is not exact as u16"
+    /// 70000usize.expect_as::<u16>("length fits the header");
+    /// ```
+    #[inline]
+    #[track_caller]
+    fn expect_as<T: AsPrim>(self, msg: &str) -> T {
+        let y: T = self.as_();
+        if !cmp::is_exact(self, y) {
+            panic!("{}: {} ({}) is not exact as {}", msg, self, Self::NAME, T::NAME);
+        }
+        y
+    }
+}
+
+#[inline(always)]
+fn is_negative<T: PartialOrd + Default>(x: T) -> bool {
+    x < T::default()
+}
+
+// The widest primitive integer types
+#[cfg(feature = "i128")]
+type Signed = i128;
+#[cfg(feature = "i128")]
+type Unsigned = u128;
+#[cfg(not(feature = "i128"))]
+type Signed = i64;
+#[cfg(not(feature = "i128"))]
+type Unsigned = u64;
+
+mod sealed {
+    /// The private supertrait of `PrimFloat`, which keeps other crates
+    /// from implementing it.
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+
+    /// The private supertrait of `PrimBytes`, which keeps other crates
+    /// from implementing it.
+    pub trait SealedBytes {}
+}
+
+// The float type of the intermediate results of the helper functions
+#[cfg(not(feature = "prefer-f32"))]
+type Float = f64;
+#[cfg(feature = "prefer-f32")]
+type Float = f32;
+
+// The body of a checked cast of `$x`, not counted in metrics
+macro_rules! checked_value {
+    (int $from:ty, int $to:ty, $x:expr) => {{
+        let v: $from = $x;
+        let x = v as $to;
+        if x as $from == v && is_negative(v) == is_negative(x) {
+            Some(x)
+        } else {
+            None
+        }
+    }};
+    (int $from:ty, float $to:ty, $x:expr) => {{
+        let v: $from = $x;
+        let x = v as $to;
+        if x.is_infinite() { None } else { Some(x) }
+    }};
+    (float $from:ty, int $to:ty, $x:expr) => {{
+        let v: $from = $x;
+        // The bounds are powers of two, so they are exact in any float
+        // type; `min - 1.` is only exact when it matters.
+        let min = <$to>::MIN as $from;
+        let max_plus_one = (<$to>::MAX / 2 + 1) as $from * 2.;
+        if (v > min - 1. || v >= min) && v < max_plus_one {
+            Some(v as $to)
+        } else {
+            None
+        }
+    }};
+    (float $from:ty, float $to:ty, $x:expr) => {{
+        let v: $from = $x;
+        let x = v as $to;
+        if x.is_infinite() && !v.is_infinite() { None } else { Some(x) }
+    }};
+}
+
+macro_rules! checked_method {
+    ($fk:ident $from:ty, $tk:ident $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> Option<$to> {
+            let y = checked_value!($fk $from, $tk $to, self);
+            #[cfg(feature = "metrics")]
+            if y.is_none() {
+                metrics::record_failed(<$to as value::KindOf>::KIND);
+            }
+            y
+        }
+    };
+}
+
+macro_rules! saturating_method {
+    (int $from:ty, int $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> $to {
+            // Clamp in the source type to the part of the target's range
+            // that it holds. The bounds are constants, so this compiles to
+            // min and max, or to nothing, instead of branches.
+            let from = <$from as value::KindOf>::KIND;
+            let to = <$to as value::KindOf>::KIND;
+            let lo: $from = if !from.is_signed() || !to.is_signed() {
+                0
+            } else if to.bits() >= from.bits() {
+                <$from>::MIN
+            } else {
+                <$to>::MIN as $from
+            };
+            let hi: $from = if to.digits() >= from.digits() {
+                <$from>::MAX
+            } else {
+                <$to>::MAX as $from
+            };
+            #[cfg(feature = "metrics")]
+            if self < lo || self > hi {
+                metrics::record_saturated(to);
+            }
+            let x = if self < lo { lo } else { self };
+            (if x > hi { hi } else { x }) as $to
+        }
+    };
+    (int $from:ty, float $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> $to {
+            let y = checked_value!(int $from, float $to, self);
+            #[cfg(feature = "metrics")]
+            if y.is_none() {
+                metrics::record_saturated(<$to as value::KindOf>::KIND);
+            }
+            match y {
+                Some(x) => x,
+                None => <$to>::MAX,
+            }
+        }
+    };
+    (float $from:ty, int $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> $to {
+            let y = checked_value!(float $from, int $to, self);
+            #[cfg(feature = "metrics")]
+            if y.is_none() {
+                metrics::record_saturated(<$to as value::KindOf>::KIND);
+            }
+            match y {
+                Some(x) => x,
+                None if self.is_nan() => 0,
+                None if self < 0. => <$to>::MIN,
+                None => <$to>::MAX,
+            }
+        }
+    };
+    (float $from:ty, float $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> $to {
+            let y = checked_value!(float $from, float $to, self);
+            #[cfg(feature = "metrics")]
+            if y.is_none() {
+                metrics::record_saturated(<$to as value::KindOf>::KIND);
+            }
+            match y {
+                Some(x) => x,
+                None if self < 0. => <$to>::MIN,
+                None => <$to>::MAX,
+            }
+        }
+    };
+}
+
+macro_rules! exact_method {
+    (int $from:ty, $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> Option<$to> {
+            let x = self as $to;
+            if checked_value!(float $to, int $from, x) == Some(self) { Some(x) } else { None }
+        }
+    };
+    (float $from:ty, $method:ident $to:ty) => {
+        #[inline]
+        fn $method(self) -> Option<$to> {
+            let x = self as $to;
+            if x as $from == self || self.is_nan() { Some(x) } else { None }
+        }
+    };
+}
+
+macro_rules! checked_methods {
+    ($kind:ident $from:ty) => {
+        checked_method!{$kind $from, int as_usize_checked usize}
+        checked_method!{$kind $from, int as_isize_checked isize}
+        #[cfg(feature = "i128")]
+        checked_method!{$kind $from, int as_u128_checked u128}
+        #[cfg(feature = "i128")]
+        checked_method!{$kind $from, int as_i128_checked i128}
+        checked_method!{$kind $from, int as_u64_checked u64}
+        checked_method!{$kind $from, int as_i64_checked i64}
+        checked_method!{$kind $from, int as_u32_checked u32}
+        checked_method!{$kind $from, int as_i32_checked i32}
+        checked_method!{$kind $from, int as_u16_checked u16}
+        checked_method!{$kind $from, int as_i16_checked i16}
+        checked_method!{$kind $from, int as_u8_checked u8}
+        checked_method!{$kind $from, int as_i8_checked i8}
+        checked_method!{$kind $from, float as_f32_checked f32}
+        checked_method!{$kind $from, float as_f64_checked f64}
+    }
+}
+
+macro_rules! saturating_methods {
+    ($kind:ident $from:ty) => {
+        saturating_method!{$kind $from, int as_usize_saturating usize}
+        saturating_method!{$kind $from, int as_isize_saturating isize}
+        #[cfg(feature = "i128")]
+        saturating_method!{$kind $from, int as_u128_saturating u128}
+        #[cfg(feature = "i128")]
+        saturating_method!{$kind $from, int as_i128_saturating i128}
+        saturating_method!{$kind $from, int as_u64_saturating u64}
+        saturating_method!{$kind $from, int as_i64_saturating i64}
+        saturating_method!{$kind $from, int as_u32_saturating u32}
+        saturating_method!{$kind $from, int as_i32_saturating i32}
+        saturating_method!{$kind $from, int as_u16_saturating u16}
+        saturating_method!{$kind $from, int as_i16_saturating i16}
+        saturating_method!{$kind $from, int as_u8_saturating u8}
+        saturating_method!{$kind $from, int as_i8_saturating i8}
+        saturating_method!{$kind $from, float as_f32_saturating f32}
+        saturating_method!{$kind $from, float as_f64_saturating f64}
+    }
 }
 
 macro_rules! as_prim_impl {
-    ($($method:ident $from:ty)*) => {
+    ($($kind:ident $method:ident $checked:ident $saturating:ident $from:ty)*) => {
         $(
             impl AsPrim for $from {
+                const KIND: PrimKind = <$from as value::KindOf>::KIND;
+                const BOUNDARY_VALUES: &'static [Self] =
+                    <$from as boundary::BoundaryValues>::VALUES;
                 #[inline(always)]
                 fn as_usize(self) -> usize { self as usize }
                 #[inline(always)]
                 fn as_isize(self) -> isize { self as isize }
+                #[cfg(feature = "i128")]
                 #[inline(always)]
                 fn as_u128(self) -> u128 { self as u128 }
+                #[cfg(feature = "i128")]
                 #[inline(always)]
                 fn as_i128(self) -> i128 { self as i128 }
                 #[inline(always)]
@@ -85,25 +816,955 @@ macro_rules! as_prim_impl {
                 fn as_f32(self) -> f32 { self as f32 }
                 #[inline(always)]
                 fn as_f64(self) -> f64 { self as f64 }
+                checked_methods!{$kind $from}
+                saturating_methods!{$kind $from}
+                exact_method!{$kind $from, as_f32_exact f32}
+                exact_method!{$kind $from, as_f64_exact f64}
                 #[inline(always)]
                 fn cast_from<T: AsPrim>(x: T) -> Self {
                     x.$method()
                 }
+                #[inline(always)]
+                fn checked_cast_from<T: AsPrim>(x: T) -> Option<Self> {
+                    x.$checked()
+                }
+                #[inline(always)]
+                fn saturating_cast_from<T: AsPrim>(x: T) -> Self {
+                    x.$saturating()
+                }
             }
         )*
     }
 }
 
-as_prim_impl!{as_u8 u8 as_i8 i8 as_u16 u16 as_i16 i16 as_u32 u32 as_i32 i32
-              as_u128 u128 as_i128 i128
-              as_u64 u64 as_i64 i64 as_usize usize as_isize isize as_f32 f32 as_f64 f64}
+as_prim_impl!{int as_u8 as_u8_checked as_u8_saturating u8
+              int as_i8 as_i8_checked as_i8_saturating i8
+              int as_u16 as_u16_checked as_u16_saturating u16
+              int as_i16 as_i16_checked as_i16_saturating i16
+              int as_u32 as_u32_checked as_u32_saturating u32
+              int as_i32 as_i32_checked as_i32_saturating i32
+              int as_u64 as_u64_checked as_u64_saturating u64
+              int as_i64 as_i64_checked as_i64_saturating i64
+              int as_usize as_usize_checked as_usize_saturating usize
+              int as_isize as_isize_checked as_isize_saturating isize
+              float as_f32 as_f32_checked as_f32_saturating f32
+              float as_f64 as_f64_checked as_f64_saturating f64}
+
+#[cfg(feature = "i128")]
+as_prim_impl!{int as_u128 as_u128_checked as_u128_saturating u128
+              int as_i128 as_i128_checked as_i128_saturating i128}
+
+/// Construct a primitive numeric type from any other using `as`.
+///
+/// `FromPrim` is implemented for every `AsPrim` type, and is the same
+/// conversion as `AsPrim::cast_from` seen from the target's side.
+///
+/// ```
+/// use asprim::FromPrim;
+///
+/// fn build<T: FromPrim>(raw: u64) -> T {
+///     T::from_prim(raw)
+/// }
+///
+/// assert_eq!(build::<f32>(3), 3.);
+/// ```
+pub trait FromPrim {
+    fn from_prim<T: AsPrim>(x: T) -> Self;
+}
+
+impl<P: AsPrim> FromPrim for P {
+    #[inline(always)]
+    fn from_prim<T: AsPrim>(x: T) -> Self {
+        P::cast_from(x)
+    }
+}
+
+/// Cast from the primitive numeric type `T` using `as`.
+///
+/// Like `From`, but for the lossy cast of `AsPrim`.
+pub trait CastFrom<T> {
+    fn from_cast(x: T) -> Self;
+}
+
+/// Cast into the primitive numeric type `T` using `as`.
+///
+/// Like `Into`, the target type can be picked by inference.
+///
+/// ```
+/// use asprim::CastInto;
+///
+/// let x = 258;
+/// let y: u8 = x.cast_into();
+/// assert_eq!(y, 2);
+/// ```
+pub trait CastInto<T> {
+    fn cast_into(self) -> T;
+}
+
+impl<T: AsPrim, U: AsPrim> CastFrom<T> for U {
+    #[inline(always)]
+    fn from_cast(x: T) -> Self {
+        U::cast_from(x)
+    }
+}
+
+impl<T: AsPrim, U: AsPrim> CastInto<U> for T {
+    #[inline(always)]
+    fn cast_into(self) -> U {
+        U::cast_from(self)
+    }
+}
+
+/// Primitive integer types.
+pub trait PrimInt : AsPrim {
+    /// The unsigned integer type with the same width as `Self`.
+    type Unsigned: PrimInt<Unsigned = Self::Unsigned, Signed = Self::Signed>;
+    /// The signed integer type with the same width as `Self`.
+    type Signed: PrimInt<Unsigned = Self::Unsigned, Signed = Self::Signed>;
+
+    /// Split self into its sign, `true` if negative, and its magnitude.
+    ///
+    /// The magnitude is unsigned, so it holds the magnitude of `MIN` too.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!((-5i32).split_sign(), (true, 5u32));
+    /// assert_eq!(i8::MIN.split_sign(), (true, 128u8));
+    /// assert_eq!(200u8.split_sign(), (false, 200u8));
+    /// ```
+    fn split_sign(self) -> (bool, Self::Unsigned);
+
+    /// Join a sign and magnitude into a value, the inverse of
+    /// `split_sign`, or return `None` if it is out of range.
+    ///
+    /// Zero may have either sign.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!(i8::join_sign(true, 128), Some(i8::MIN));
+    /// assert_eq!(i8::join_sign(false, 128), None);
+    /// assert_eq!(u8::join_sign(true, 0), Some(0));
+    /// assert_eq!(u8::join_sign(true, 1), None);
+    /// ```
+    fn join_sign(negative: bool, magnitude: Self::Unsigned) -> Option<Self>;
+
+    /// Return the absolute value of self, as the unsigned type of the same
+    /// width, so that it holds the magnitude of `MIN` too.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!(i8::MIN.unsigned_abs_as(), 128u8);
+    /// assert_eq!((-3i64).unsigned_abs_as(), 3u64);
+    /// assert_eq!(u16::MAX.unsigned_abs_as(), u16::MAX);
+    /// ```
+    #[inline]
+    fn unsigned_abs_as(self) -> Self::Unsigned {
+        self.split_sign().1
+    }
+
+    /// Reinterpret the bits of self as the signed type of the same width.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!(0xffff_fffeu32.reinterpret_signed(), -2i32);
+    /// assert_eq!((-2i32).reinterpret_signed(), -2i32);
+    /// ```
+    #[inline]
+    fn reinterpret_signed(self) -> Self::Signed {
+        // `as` between integers of the same width keeps the bits
+        self.as_()
+    }
+
+    /// Reinterpret the bits of self as the unsigned type of the same width.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!((-2i32).reinterpret_unsigned(), 0xffff_fffeu32);
+    /// assert_eq!(i8::MIN.reinterpret_unsigned(), 0x80u8);
+    /// ```
+    #[inline]
+    fn reinterpret_unsigned(self) -> Self::Unsigned {
+        self.as_()
+    }
+
+    /// Return the `char` with self as its code point, or `None` if it is
+    /// not a valid code point.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!(0x41u64.as_char_checked(), Some('A'));
+    /// assert_eq!(0xd800i32.as_char_checked(), None);
+    /// assert_eq!((-1i8).as_char_checked(), None);
+    /// ```
+    #[inline]
+    fn as_char_checked(self) -> Option<char> {
+        self.as_u32_checked().and_then(core::char::from_u32)
+    }
+
+    /// Cast self to `f64`, rounding with `mode` if it is not exact, and
+    /// return the result with its order relative to self: `Less` if it
+    /// was rounded down, `Greater` if up, and `Equal` if it is exact.
+    ///
+    /// `as` rounds like `RoundingMode::NearestEven`. This is exact around
+    /// the limits of the 128-bit integers too; `u128::MAX` rounds up to
+    /// 2^128 except when rounding toward zero or down.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use asprim::{PrimInt, RoundingMode};
+    ///
+    /// let x = (1u64 << 53) + 1;
+    /// assert_eq!(x.as_f64_rounded(RoundingMode::NearestEven), (x as f64, Ordering::Less));
+    /// assert_eq!(x.as_f64_rounded(RoundingMode::Up), (9007199254740994., Ordering::Greater));
+    /// assert_eq!((-(x as i64)).as_f64_rounded(RoundingMode::NearestAway),
+    ///            (-9007199254740994., Ordering::Less));
+    /// assert_eq!(7i32.as_f64_rounded(RoundingMode::Down), (7., Ordering::Equal));
+    /// ```
+    #[inline]
+    fn as_f64_rounded(self, mode: RoundingMode) -> (f64, Ordering) {
+        let (negative, m) = self.split_sign();
+        round::int_to_f64(negative, m.as_(), mode)
+    }
+
+    /// Cast `x` to `Self` if it is an integer in range, or return `None`
+    /// if the cast would round it, or it is out of range or NaN.
+    ///
+    /// ```
+    /// use asprim::PrimInt;
+    ///
+    /// assert_eq!(i64::from_f64_exact(-2f64.powi(63)), Some(i64::MIN));
+    /// assert_eq!(i64::from_f64_exact(2f64.powi(63)), None);
+    /// assert_eq!(u8::from_f64_exact(2.5), None);
+    /// assert_eq!(u8::from_f64_exact(-0.), Some(0));
+    /// ```
+    #[inline]
+    fn from_f64_exact(x: f64) -> Option<Self> {
+        cmp::checked(x).filter(|&y| cmp::is_exact(x, y))
+    }
+    #[doc(hidden)]
+    unsafe fn from_f32_unchecked(x: f32) -> Self;
+    #[doc(hidden)]
+    unsafe fn from_f64_unchecked(x: f64) -> Self;
+}
+
+/// Primitive floating point types.
+///
+/// The trait is sealed: only `f32` and `f64` implement it, so that
+/// functions like `as_bits_slice` can rely on `Bits` having the same size
+/// and alignment as the float.
+///
+/// ```compile_fail
+/// #[macro_use] extern crate asprim;
+///
+/// use std::fmt;
+/// use asprim::{PrimFloat, PrimInt};
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+/// struct Tiny(f32);
+///
+/// impl fmt::Display for Tiny {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         self.0.fmt(f)
+///     }
+/// }
+///
+/// impl_as_prim!(Tiny, repr: f32, into: |t: Tiny| t.0, from: |r: f32| Tiny(r),
+///               min: f32::MIN, max: f32::MAX);
+///
+/// // Error: a `u64` is larger than a `Tiny`, and the trait is sealed
+/// impl PrimFloat for Tiny {
+///     type Bits = u64;
+///
+///     unsafe fn as_int_unchecked<T: PrimInt>(self) -> T {
+///         self.0.as_int_unchecked()
+///     }
+///     fn decompose(self) -> (bool, u64, i32) {
+///         self.0.decompose()
+///     }
+///     fn recompose(negative: bool, mantissa: u64, exponent: i32) -> Option<Self> {
+///         f32::recompose(negative, mantissa, exponent).map(Tiny)
+///     }
+/// }
+/// # fn main() {}
+/// ```
+pub trait PrimFloat : AsPrim + sealed::Sealed {
+    /// The unsigned integer type with the same size and alignment as
+    /// `Self`, which holds its bit pattern.
+    type Bits: PrimInt;
+
+    /// Cast self to the integer type `T` without checking the range,
+    /// using `to_int_unchecked`.
+    ///
+    /// Since Rust 1.45, float to integer `as` casts saturate, which costs a
+    /// few instructions per conversion. This is the opt-in non-saturating
+    /// alternative for code that has already proven its inputs are in
+    /// range. There is no safe variant: any safe conversion has to produce
+    /// *some* value for NaN and out of range inputs, and that is exactly
+    /// the work `as` already does.
+    ///
+    /// ```
+    /// use asprim::PrimFloat;
+    ///
+    /// let x = unsafe { 255.9f32.as_int_unchecked::<u8>() };
+    /// assert_eq!(x, 255);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The value must not be NaN or infinite, and it must be representable
+    /// in `T` after truncating its fractional part.
+    unsafe fn as_int_unchecked<T: PrimInt>(self) -> T;
+
+    /// Split self into its sign, `true` if negative, and an integer
+    /// mantissa and binary exponent, so that its magnitude is
+    /// `mantissa * 2^exponent`.
+    ///
+    /// The mantissa has at most `MANTISSA_DIGITS` bits, and has its leading
+    /// bit set unless self is zero or subnormal, whose exponent is that of
+    /// the smallest subnormal number. The decomposition is unique except
+    /// for NaN and the infinities, which decompose as if their exponent
+    /// field held an ordinary exponent, and so do not recompose.
+    ///
+    /// ```
+    /// use asprim::PrimFloat;
+    ///
+    /// assert_eq!(1.5f32.decompose(), (false, 3 << 22, -23));
+    /// assert_eq!((-1f64).decompose(), (true, 1 << 52, -52));
+    /// assert_eq!(f32::from_bits(1).decompose(), (false, 1, -149));
+    /// assert_eq!((-0f64).decompose(), (true, 0, -1074));
+    /// ```
+    fn decompose(self) -> (bool, u64, i32);
+
+    /// Join a sign, integer mantissa and binary exponent into the value
+    /// `mantissa * 2^exponent`, the inverse of `decompose`, or return
+    /// `None` if it is not exactly representable in `Self`.
+    ///
+    /// The mantissa need not be normalized.
+    ///
+    /// ```
+    /// use asprim::PrimFloat;
+    ///
+    /// assert_eq!(f32::recompose(false, 3, -1), Some(1.5));
+    /// assert_eq!(f64::recompose(true, 1 << 60, -60), Some(-1.));
+    /// assert_eq!(f32::recompose(false, (1 << 24) + 1, 0), None);
+    /// assert_eq!(f32::recompose(false, 1, 128), None);
+    /// assert_eq!(f32::recompose(false, 1, -150), None);
+    ///
+    /// let x = 0.1f64;
+    /// let (sign, mantissa, exponent) = x.decompose();
+    /// assert_eq!(f64::recompose(sign, mantissa, exponent), Some(x));
+    /// ```
+    fn recompose(negative: bool, mantissa: u64, exponent: i32) -> Option<Self>;
+
+    /// Round self to an integer using `mode`.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, RoundingMode};
+    ///
+    /// assert_eq!((-2.5f32).round_with(RoundingMode::Down), -3.);
+    /// assert_eq!((-2.5f32).round_with(RoundingMode::NearestEven), -2.);
+    /// assert_eq!((-2.5f32).round_with(RoundingMode::NearestAway), -3.);
+    /// ```
+    ///
+    /// Requires the `libm` feature.
+    #[cfg(feature = "libm")]
+    #[inline]
+    fn round_with(self, mode: RoundingMode) -> Self {
+        // Rounding the f64 is exact: the integers near a float are
+        // representable in its own type.
+        Self::cast_from(round::round_f64(self.as_f64(), mode))
+    }
+
+    /// Round self to an integer using `mode`, then cast to the type `T`
+    /// using `as`.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, RoundingMode};
+    ///
+    /// assert_eq!(254.5f64.round_as::<u8>(RoundingMode::Up), 255);
+    /// assert_eq!(255.5f64.round_as::<u8>(RoundingMode::Up), 255);
+    /// ```
+    ///
+    /// Requires the `libm` feature.
+    #[cfg(feature = "libm")]
+    #[inline]
+    fn round_as<T: AsPrim>(self, mode: RoundingMode) -> T {
+        self.round_with(mode).as_()
+    }
+
+    /// Round self to an integer using `mode`, then cast to the type `T`, or
+    /// return `None` if it is out of range, like `checked_as`.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, RoundingMode};
+    ///
+    /// assert_eq!(254.5f64.checked_round_as::<u8>(RoundingMode::Up), Some(255));
+    /// assert_eq!(255.5f64.checked_round_as::<u8>(RoundingMode::Up), None);
+    /// ```
+    ///
+    /// Requires the `libm` feature.
+    #[cfg(feature = "libm")]
+    #[inline]
+    fn checked_round_as<T: AsPrim>(self, mode: RoundingMode) -> Option<T> {
+        self.round_with(mode).checked_as()
+    }
+
+    /// Cast self to the type `T` like `checked_as`, with `non_finite`
+    /// deciding the result for NaN and infinities.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, NonFinite};
+    ///
+    /// assert_eq!(f32::NAN.checked_as_with::<u8>(NonFinite::Zero), Some(0));
+    /// assert_eq!(1e10f32.checked_as_with::<u8>(NonFinite::Zero), None);
+    /// ```
+    #[inline]
+    fn checked_as_with<T: AsPrim>(self, non_finite: NonFinite) -> Option<T> {
+        if self.as_f64().is_finite() {
+            self.checked_as()
+        } else {
+            non_finite.cast(self)
+        }
+    }
+
+    /// Cast self to the type `T` like `saturating_as`, with `non_finite`
+    /// deciding the result for NaN and infinities.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, NonFinite};
+    ///
+    /// assert_eq!(f32::INFINITY.saturating_as_with::<u8>(NonFinite::Fail), None);
+    /// assert_eq!(1e10f32.saturating_as_with::<u8>(NonFinite::Fail), Some(255));
+    /// ```
+    #[inline]
+    fn saturating_as_with<T: AsPrim>(self, non_finite: NonFinite) -> Option<T> {
+        if self.as_f64().is_finite() {
+            Some(self.saturating_as())
+        } else {
+            non_finite.cast(self)
+        }
+    }
+
+    /// Multiply self by `10^decimals` and round it to the integer type
+    /// `T` using `mode`, or return `None` if it is out of range, NaN or
+    /// infinite, or `decimals` is more than 38.
+    ///
+    /// This is the conversion of an amount to a count of its smallest
+    /// units, like euros to cents. It rounds the exact value of self once,
+    /// where `(self * 100.).round()` rounds the product first: 0.015 is a
+    /// little less than 0.015 as a float, and so rounds to one cent.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, RoundingMode};
+    ///
+    /// assert_eq!(19.99f64.to_scaled_int::<i64>(2, RoundingMode::NearestEven), Some(1999));
+    /// assert_eq!(0.015f64.to_scaled_int::<i64>(2, RoundingMode::NearestAway), Some(1));
+    /// assert_eq!((0.015f64 * 100.).round(), 2.);
+    /// assert_eq!((-2.5f32).to_scaled_int::<i32>(0, RoundingMode::Down), Some(-3));
+    /// assert_eq!(1e17f64.to_scaled_int::<i64>(2, RoundingMode::NearestEven), None);
+    /// ```
+    #[inline]
+    fn to_scaled_int<T: PrimInt>(self, decimals: u32, mode: RoundingMode) -> Option<T> {
+        scaled::to_scaled_int(self, decimals, mode)
+    }
+
+    /// Divide `n` by `10^decimals` and round it to `Self` using `mode`, or
+    /// return `None` if it is out of range, or `decimals` is more than 38.
+    /// Only `f32` can be out of range, for `u128` values near `u128::MAX`.
+    ///
+    /// The inverse of `to_scaled_int`, converting a count of the smallest
+    /// units of an amount back to the amount. The result is the float
+    /// closest to the exact quotient in the direction of `mode`.
+    ///
+    /// ```
+    /// use asprim::{PrimFloat, RoundingMode};
+    ///
+    /// assert_eq!(f64::from_scaled_int(1999i64, 2, RoundingMode::NearestEven), Some(19.99));
+    /// assert_eq!(f64::from_scaled_int(1u8, 1, RoundingMode::Down), Some(0.09999999999999999));
+    /// assert_eq!(f32::from_scaled_int(u64::MAX, 0, RoundingMode::TowardZero), Some(1.8446743e19));
+    /// assert_eq!(f32::from_scaled_int(-5i8, 40, RoundingMode::NearestEven), None);
+    /// ```
+    #[inline]
+    fn from_scaled_int<T: PrimInt>(n: T, decimals: u32, mode: RoundingMode) -> Option<Self> {
+        scaled::from_scaled_int(n, decimals, mode)
+    }
+}
+
+/// How to cast values that are out of range for the target type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CastPolicy {
+    /// Cast using `as`, like `AsPrim::as_`.
+    As,
+    /// Clamp to the range of the target, like `AsPrim::saturating_as`.
+    Saturating,
+}
+
+impl CastPolicy {
+    /// Cast `x` to the type `U` using this policy.
+    #[inline]
+    pub fn cast<T: AsPrim, U: AsPrim>(self, x: T) -> U {
+        match self {
+            CastPolicy::As => x.as_(),
+            CastPolicy::Saturating => x.saturating_as(),
+        }
+    }
+}
+
+/// What to do with NaN and infinities in `PrimFloat::checked_as_with` and
+/// `PrimFloat::saturating_as_with`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NonFinite {
+    /// Fail the cast, returning `None`.
+    Fail,
+    /// Cast to zero.
+    Zero,
+    /// Cast using `saturating_as`: infinities become the largest or
+    /// smallest value of an integer target, and NaN becomes zero. Float
+    /// targets keep the value.
+    Saturate,
+}
+
+impl NonFinite {
+    #[inline]
+    fn cast<F: AsPrim, T: AsPrim>(self, x: F) -> Option<T> {
+        match self {
+            NonFinite::Fail => None,
+            NonFinite::Zero => Some(T::cast_from(0)),
+            NonFinite::Saturate => Some(x.saturating_as()),
+        }
+    }
+}
+
+macro_rules! prim_int_impl {
+    ($($u:ident $i:ident)*) => {
+        $(
+            impl PrimInt for $u {
+                type Unsigned = $u;
+                type Signed = $i;
+
+                #[inline]
+                fn split_sign(self) -> (bool, $u) {
+                    (false, self)
+                }
+                #[inline]
+                fn join_sign(negative: bool, magnitude: $u) -> Option<Self> {
+                    if negative && magnitude != 0 {
+                        None
+                    } else {
+                        Some(magnitude)
+                    }
+                }
+                prim_int_impl!{@unchecked}
+            }
+
+            impl PrimInt for $i {
+                type Unsigned = $u;
+                type Signed = $i;
+
+                #[inline]
+                fn split_sign(self) -> (bool, $u) {
+                    (self < 0, self.unsigned_abs())
+                }
+                #[inline]
+                fn join_sign(negative: bool, magnitude: $u) -> Option<Self> {
+                    if !negative && magnitude <= $i::MAX as $u {
+                        Some(magnitude as $i)
+                    } else if negative && magnitude <= $i::MIN.unsigned_abs() {
+                        Some((magnitude as $i).wrapping_neg())
+                    } else {
+                        None
+                    }
+                }
+                prim_int_impl!{@unchecked}
+            }
+        )*
+    };
+    (@unchecked) => {
+        #[inline(always)]
+        unsafe fn from_f32_unchecked(x: f32) -> Self {
+            x.to_int_unchecked()
+        }
+        #[inline(always)]
+        unsafe fn from_f64_unchecked(x: f64) -> Self {
+            x.to_int_unchecked()
+        }
+    };
+}
+
+prim_int_impl!{u8 i8 u16 i16 u32 i32 u64 i64 usize isize}
+#[cfg(feature = "i128")]
+prim_int_impl!{u128 i128}
+
+impl PrimFloat for f32 {
+    type Bits = u32;
+
+    #[inline(always)]
+    unsafe fn as_int_unchecked<T: PrimInt>(self) -> T {
+        T::from_f32_unchecked(self)
+    }
+
+    #[inline]
+    fn decompose(self) -> (bool, u64, i32) {
+        float_parts::decompose(self.to_bits() as u64, 32, f32::MANTISSA_DIGITS, f32::MAX_EXP)
+    }
+
+    #[inline]
+    fn recompose(negative: bool, mantissa: u64, exponent: i32) -> Option<Self> {
+        float_parts::recompose(negative, mantissa, exponent, 32, f32::MANTISSA_DIGITS, f32::MAX_EXP)
+            .map(|bits| f32::from_bits(bits as u32))
+    }
+}
+
+impl PrimFloat for f64 {
+    type Bits = u64;
+
+    #[inline(always)]
+    unsafe fn as_int_unchecked<T: PrimInt>(self) -> T {
+        T::from_f64_unchecked(self)
+    }
+
+    #[inline]
+    fn decompose(self) -> (bool, u64, i32) {
+        float_parts::decompose(self.to_bits(), 64, f64::MANTISSA_DIGITS, f64::MAX_EXP)
+    }
+
+    #[inline]
+    fn recompose(negative: bool, mantissa: u64, exponent: i32) -> Option<Self> {
+        float_parts::recompose(negative, mantissa, exponent, 64, f64::MANTISSA_DIGITS, f64::MAX_EXP)
+            .map(f64::from_bits)
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use core::fmt;
     use super::AsPrim;
+
+    #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+    struct U12(u16);
+
+    impl fmt::Display for U12 {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl_as_prim!(U12, repr: u16,
+                  into: |x: U12| x.0,
+                  from: |r: u16| U12(r & 0xfff),
+                  min: 0, max: 0xfff);
+
+    #[test]
+    fn external_impl() {
+        assert_eq!(U12(4095).as_i8(), -1);
+        assert_eq!(4096.as_::<U12>(), U12(0));
+        assert_eq!(4096.checked_as::<U12>(), None);
+        assert_eq!(4095.5f32.checked_as::<U12>(), Some(U12(4095)));
+        assert_eq!((-1).saturating_as::<U12>(), U12(0));
+        assert_eq!(1e9.saturating_as::<U12>(), U12(4095));
+        assert_eq!(U12(100).as_::<U12>(), U12(100));
+        assert_eq!(U12(300).as_u8_checked(), None);
+        #[cfg(feature = "test-util")]
+        ::test_util::verify_asprim_impl(&[U12(0), U12(1), U12(255), U12(256), U12(4095)]);
+    }
+
     #[test]
     fn it_works() {
         assert_eq!(1.as_::<f32>(), 1.0);
+        #[cfg(feature = "i128")]
         assert_eq!(1i128.as_::<f32>(), 1.0);
     }
+
+    #[test]
+    fn checked() {
+        assert_eq!(255.checked_as::<u8>(), Some(255u8));
+        assert_eq!(256.checked_as::<u8>(), None);
+        assert_eq!((-1i8).as_u64_checked(), None);
+        assert_eq!((-128i32).as_i8_checked(), Some(-128));
+        assert_eq!(u64::MAX.as_i64_checked(), None);
+        #[cfg(feature = "i128")]
+        assert_eq!(u128::MAX.as_f32_checked(), None);
+        #[cfg(feature = "i128")]
+        assert_eq!(u128::MAX.as_f64_checked(), Some(u128::MAX as f64));
+        assert_eq!((-0.9f32).as_u8_checked(), Some(0));
+        assert_eq!((-1.0f32).as_u8_checked(), None);
+        assert_eq!(255.9f64.as_u8_checked(), Some(255));
+        assert_eq!(256.0f64.as_u8_checked(), None);
+        assert_eq!((-128.5f32).as_i8_checked(), Some(-128));
+        assert_eq!((-129.0f32).as_i8_checked(), None);
+        assert_eq!((i64::MIN as f64).as_i64_checked(), Some(i64::MIN));
+        assert_eq!((i64::MAX as f64).as_i64_checked(), None);
+        #[cfg(feature = "i128")]
+        assert_eq!(f32::MAX.as_u128_checked(), Some(f32::MAX as u128));
+        assert_eq!(f32::NAN.as_i32_checked(), None);
+        assert_eq!(f64::INFINITY.as_u64_checked(), None);
+        assert_eq!(f64::MAX.as_f32_checked(), None);
+        assert_eq!(f64::INFINITY.as_f32_checked(), Some(f32::INFINITY));
+        assert!(f64::NAN.as_f32_checked().unwrap().is_nan());
+    }
+
+    #[test]
+    fn saturating() {
+        assert_eq!(300.saturating_as::<u8>(), 255u8);
+        assert_eq!((-300).as_i8_saturating(), -128);
+        assert_eq!((-1i64).as_u32_saturating(), 0);
+        assert_eq!(u64::MAX.as_i64_saturating(), i64::MAX);
+        #[cfg(feature = "i128")]
+        assert_eq!(i128::MIN.as_isize_saturating(), isize::MIN);
+        #[cfg(feature = "i128")]
+        assert_eq!(u128::MAX.as_f32_saturating(), f32::MAX);
+        assert_eq!(255.9f32.as_u8_saturating(), 255);
+        assert_eq!(1e10f32.as_u8_saturating(), 255);
+        assert_eq!((-0.5f64).as_u8_saturating(), 0);
+        assert_eq!((-1e10f64).as_i16_saturating(), i16::MIN);
+        assert_eq!(f64::NAN.as_i32_saturating(), 0);
+        #[cfg(feature = "i128")]
+        assert_eq!(f32::INFINITY.as_u128_saturating(), u128::MAX);
+        assert_eq!(f64::MAX.as_f32_saturating(), f32::MAX);
+        assert_eq!(f64::MIN.as_f32_saturating(), f32::MIN);
+        assert_eq!(f64::NEG_INFINITY.as_f32_saturating(), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn saturating_int() {
+        fn check<T: AsPrim, U: AsPrim>() {
+            let bounds = U::BOUNDARY_VALUES;
+            let min = bounds.iter().fold(bounds[0], |m, &y| if y < m { y } else { m });
+            let max = bounds.iter().fold(bounds[0], |m, &y| if y > m { y } else { m });
+            for &x in T::BOUNDARY_VALUES {
+                let expected = match x.checked_as::<U>() {
+                    Some(y) => y,
+                    None if x < T::cast_from(0) => min,
+                    None => max,
+                };
+                assert_eq!(x.saturating_as::<U>(), expected, "{} as {}", x, U::NAME);
+            }
+        }
+        fn check_to<T: AsPrim>() {
+            check::<T, u8>();
+            check::<T, i8>();
+            check::<T, u16>();
+            check::<T, i16>();
+            check::<T, u32>();
+            check::<T, i32>();
+            check::<T, u64>();
+            check::<T, i64>();
+            #[cfg(feature = "i128")]
+            check::<T, u128>();
+            #[cfg(feature = "i128")]
+            check::<T, i128>();
+            check::<T, usize>();
+            check::<T, isize>();
+        }
+        check_to::<u8>();
+        check_to::<i8>();
+        check_to::<u16>();
+        check_to::<i16>();
+        check_to::<u32>();
+        check_to::<i32>();
+        check_to::<u64>();
+        check_to::<i64>();
+        #[cfg(feature = "i128")]
+        check_to::<u128>();
+        #[cfg(feature = "i128")]
+        check_to::<i128>();
+        check_to::<usize>();
+        check_to::<isize>();
+    }
+
+    #[test]
+    fn exact() {
+        assert_eq!((1u64 << 53).as_f64_exact(), Some(9007199254740992.));
+        assert_eq!(((1u64 << 53) + 1).as_f64_exact(), None);
+        assert_eq!(u64::MAX.as_f64_exact(), None);
+        assert_eq!(i64::MIN.as_f64_exact(), Some(i64::MIN as f64));
+        assert_eq!(16777217i32.as_f32_exact(), None);
+        assert_eq!((-16777216i32).as_f32_exact(), Some(-16777216.));
+        assert_eq!(0.5f64.as_f32_exact(), Some(0.5));
+        assert_eq!(0.1f64.as_f32_exact(), None);
+        assert_eq!(f64::MAX.as_f32_exact(), None);
+        assert_eq!(f64::INFINITY.as_f32_exact(), Some(f32::INFINITY));
+        assert!(f64::NAN.as_f32_exact().unwrap().is_nan());
+        assert_eq!(0.1f32.as_f64_exact(), Some(0.1f32 as f64));
+    }
+
+    #[test]
+    fn non_finite() {
+        use super::{PrimFloat, NonFinite};
+        for &policy in &[NonFinite::Fail, NonFinite::Zero, NonFinite::Saturate] {
+            assert_eq!(2.5f64.checked_as_with::<i8>(policy), Some(2));
+            assert_eq!(1e3f64.checked_as_with::<i8>(policy), None);
+            assert_eq!(1e3f64.saturating_as_with::<i8>(policy), Some(127));
+        }
+        assert_eq!(f64::NAN.checked_as_with::<i8>(NonFinite::Fail), None);
+        assert_eq!(f64::NAN.checked_as_with::<i8>(NonFinite::Zero), Some(0));
+        assert_eq!(f64::NAN.checked_as_with::<i8>(NonFinite::Saturate), Some(0));
+        assert_eq!(f32::NEG_INFINITY.saturating_as_with::<i8>(NonFinite::Zero), Some(0));
+        assert_eq!(f32::NEG_INFINITY.saturating_as_with::<i8>(NonFinite::Saturate),
+                   Some(-128));
+        assert_eq!(f32::INFINITY.checked_as_with::<f64>(NonFinite::Saturate),
+                   Some(f64::INFINITY));
+        assert_eq!(f32::INFINITY.checked_as_with::<f64>(NonFinite::Fail), None);
+    }
+
+    #[test]
+    fn sign() {
+        use super::PrimInt;
+
+        fn round_trip<T: PrimInt>() {
+            for &x in T::BOUNDARY_VALUES {
+                let (negative, magnitude) = x.split_sign();
+                assert_eq!(T::join_sign(negative, magnitude), Some(x));
+            }
+        }
+        round_trip::<u8>();
+        round_trip::<i8>();
+        round_trip::<i32>();
+        round_trip::<u64>();
+        round_trip::<isize>();
+        #[cfg(feature = "i128")]
+        round_trip::<i128>();
+        assert_eq!(i16::join_sign(true, 0), Some(0));
+        assert_eq!(i16::join_sign(true, 32769), None);
+    }
+
+    #[test]
+    fn reinterpret() {
+        use super::PrimInt;
+
+        fn round_trip<T: PrimInt>() {
+            for &x in T::BOUNDARY_VALUES {
+                assert_eq!(x.reinterpret_signed().reinterpret_unsigned(),
+                           x.reinterpret_unsigned());
+                assert_eq!(T::cast_from(x.reinterpret_unsigned()), x);
+            }
+        }
+        round_trip::<u16>();
+        round_trip::<i16>();
+        round_trip::<usize>();
+        round_trip::<i64>();
+        #[cfg(feature = "i128")]
+        round_trip::<u128>();
+        assert_eq!(u64::MAX.reinterpret_signed(), -1);
+    }
+
+    #[test]
+    fn f64_rounded() {
+        use core::cmp::Ordering;
+        use super::{cmp_exact, PrimInt, RoundingMode};
+
+        fn check<T: PrimInt>() {
+            for &x in T::BOUNDARY_VALUES {
+                let (even, ord) = x.as_f64_rounded(RoundingMode::NearestEven);
+                assert_eq!(even, x.as_f64());
+                assert_eq!(cmp_exact(even, x), Some(ord));
+                let (down, down_ord) = x.as_f64_rounded(RoundingMode::Down);
+                let (up, up_ord) = x.as_f64_rounded(RoundingMode::Up);
+                assert_ne!(down_ord, Ordering::Greater);
+                assert_ne!(up_ord, Ordering::Less);
+                assert!(down == up || up == f64::from_bits(down.to_bits() + 1)
+                        || down == -f64::from_bits((-up).to_bits() + 1));
+                let (zero, _) = x.as_f64_rounded(RoundingMode::TowardZero);
+                assert_eq!(zero, if AsPrim::is_negative(x) { up } else { down });
+                if ord == Ordering::Equal {
+                    assert_eq!(T::from_f64_exact(even), Some(x));
+                }
+            }
+        }
+        check::<u8>();
+        check::<i16>();
+        check::<u32>();
+        check::<i64>();
+        check::<u64>();
+        check::<isize>();
+        #[cfg(feature = "i128")]
+        {
+            check::<u128>();
+            check::<i128>();
+            assert_eq!(u128::MAX.as_f64_rounded(RoundingMode::TowardZero),
+                       (2f64.powi(128) - 2f64.powi(75), Ordering::Less));
+            assert_eq!(i128::MIN.as_f64_rounded(RoundingMode::Up),
+                       (-2f64.powi(127), Ordering::Equal));
+            assert_eq!(u128::from_f64_exact(2f64.powi(128)), None);
+        }
+        // A tie between two floats
+        let tie = (1i64 << 54) + 2;
+        assert_eq!(tie.as_f64_rounded(RoundingMode::NearestEven).0, (1i64 << 54) as f64);
+        assert_eq!(tie.as_f64_rounded(RoundingMode::NearestAway).0, ((1i64 << 54) + 4) as f64);
+        assert_eq!(i32::from_f64_exact(f64::NAN), None);
+    }
+
+    #[test]
+    fn from_methods() {
+        fn len_as<P: AsPrim>(xs: &[u8]) -> P {
+            P::from_usize(xs.len())
+        }
+        assert_eq!(len_as::<f32>(&[1, 2]), 2.);
+        assert_eq!(u8::from_f64(-1.5), 0);
+        assert_eq!(i8::from_u16(200), -56);
+        #[cfg(feature = "i128")]
+        assert_eq!(f64::from_u128(u128::MAX), 2f64.powi(128));
+    }
+
+    #[test]
+    fn cast_loss() {
+        assert_eq!(5u8.cast_loss::<i8>(), 0.);
+        assert_eq!((-1).cast_loss::<u64>(), 1.);
+        assert_eq!(f64::INFINITY.cast_loss::<f32>(), 0.);
+        assert_eq!(f64::INFINITY.cast_loss::<i8>(), f64::INFINITY);
+        assert_eq!(f32::NAN.cast_loss::<f64>(), 0.);
+        assert!(f32::NAN.cast_loss::<u8>().is_nan());
+        assert_eq!(u64::MAX.cast_loss::<u32>(), (u64::MAX - u32::MAX as u64) as f64);
+    }
+
+    #[test]
+    fn expect_as() {
+        assert_eq!((-1i64).expect_as::<i8>("fits"), -1);
+        assert!(f32::NAN.expect_as::<f64>("NaN").is_nan());
+        assert_eq!(u32::MAX.expect_as::<f64>("exact"), u32::MAX as f64);
+    }
+
+    #[test]
+    #[should_panic(expected = "sample: 2.5 (f32) is not exact as i32")]
+    fn expect_as_inexact() {
+        2.5f32.expect_as::<i32>("sample");
+    }
+
+    #[test]
+    fn sign_predicates() {
+        fn check<T: AsPrim>(x: T, zero: bool, negative: bool, signum: i8) {
+            assert_eq!(x.is_zero(), zero);
+            assert_eq!(AsPrim::is_negative(x), negative);
+            assert_eq!(x.signum_i8(), signum);
+        }
+        check(0u32, true, false, 0);
+        check(usize::MAX, false, false, 1);
+        check(i64::MIN, false, true, -1);
+        check(-0.0f32, true, false, 0);
+        check(f64::NEG_INFINITY, false, true, -1);
+        check(f32::NAN, false, false, 0);
+    }
+
+    #[test]
+    fn abs() {
+        use super::PrimInt;
+
+        fn check<T: PrimInt>(x: T, abs: T, magnitude: T::Unsigned) {
+            assert_eq!(x.abs_prim(), abs);
+            assert_eq!(x.unsigned_abs_as(), magnitude);
+        }
+        check(-1i32, 1, 1);
+        check(isize::MIN, isize::MAX, isize::MIN as usize);
+        check(u64::MAX, u64::MAX, u64::MAX);
+        #[cfg(feature = "i128")]
+        check(i128::MIN, i128::MAX, 1 << 127);
+        assert_eq!(f32::NEG_INFINITY.abs_prim(), f32::INFINITY);
+        assert!((-f64::NAN).abs_prim().is_sign_positive());
+    }
 }
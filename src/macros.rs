@@ -0,0 +1,176 @@
+/// Implement `AsPrim` for a numeric type outside this crate, by delegating
+/// to a primitive representation type.
+///
+/// The type needs conversions to and from its representation `repr`, and
+/// the range of values it can hold, given in `repr`:
+///
+/// - `into` converts a value to `repr`,
+/// - `from` converts any value of `repr` to the type, like `as` does (in
+///   this example it wraps by masking),
+/// - `min` and `max` bound the values that `from` maps exactly; checked
+///   casts fail outside them and saturating casts clamp to them,
+/// - `boundary_values`, optionally, is the type's `BOUNDARY_VALUES`; it
+///   defaults to none.
+///
+/// ```
+/// #[macro_use] extern crate asprim;
+///
+/// use std::fmt;
+/// use asprim::AsPrim;
+///
+/// const MASK: u64 = (1 << 40) - 1;
+///
+/// #[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+/// struct Timestamp40(u64);
+///
+/// impl fmt::Display for Timestamp40 {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         self.0.fmt(f)
+///     }
+/// }
+///
+/// impl_as_prim!(Timestamp40, repr: u64,
+///               into: |t: Timestamp40| t.0,
+///               from: |r: u64| Timestamp40(r & MASK),
+///               min: 0, max: MASK,
+///               boundary_values: &[Timestamp40(0), Timestamp40(MASK)]);
+///
+/// # fn main() {
+/// assert_eq!(Timestamp40(1 << 39).as_f64(), 549755813888.);
+/// assert_eq!((-1).as_::<Timestamp40>(), Timestamp40(MASK));
+/// assert_eq!((-1).checked_as::<Timestamp40>(), None);
+/// assert_eq!(u64::MAX.saturating_as::<Timestamp40>(), Timestamp40(MASK));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_as_prim {
+    ($t:ty, repr: $repr:ty, into: $into:expr, from: $from:expr,
+     min: $min:expr, max: $max:expr $(,)*) => {
+        impl_as_prim!($t, repr: $repr, into: $into, from: $from, min: $min, max: $max,
+                      boundary_values: &[]);
+    };
+    ($t:ty, repr: $repr:ty, into: $into:expr, from: $from:expr,
+     min: $min:expr, max: $max:expr, boundary_values: $boundary:expr $(,)*) => {
+        impl $crate::AsPrim for $t {
+            const KIND: $crate::PrimKind = <$repr as $crate::AsPrim>::KIND;
+            const BOUNDARY_VALUES: &'static [Self] = $boundary;
+            $crate::__asprim_delegate!{$t, $repr, $into;
+                as_usize usize, as_isize isize,
+                as_u64 u64, as_i64 i64, as_u32 u32, as_i32 i32,
+                as_u16 u16, as_i16 i16, as_u8 u8, as_i8 i8,
+                as_f32 f32, as_f64 f64,
+                as_usize_saturating usize, as_isize_saturating isize,
+                as_u64_saturating u64, as_i64_saturating i64,
+                as_u32_saturating u32, as_i32_saturating i32,
+                as_u16_saturating u16, as_i16_saturating i16,
+                as_u8_saturating u8, as_i8_saturating i8,
+                as_f32_saturating f32, as_f64_saturating f64}
+            $crate::__asprim_delegate!{$t, $repr, $into;
+                as_usize_checked Option<usize>, as_isize_checked Option<isize>,
+                as_u64_checked Option<u64>, as_i64_checked Option<i64>,
+                as_u32_checked Option<u32>, as_i32_checked Option<i32>,
+                as_u16_checked Option<u16>, as_i16_checked Option<i16>,
+                as_u8_checked Option<u8>, as_i8_checked Option<i8>,
+                as_f32_checked Option<f32>, as_f64_checked Option<f64>,
+                as_f32_exact Option<f32>, as_f64_exact Option<f64>}
+            $crate::__asprim_if_i128!{
+                $crate::__asprim_delegate!{$t, $repr, $into;
+                    as_u128 u128, as_i128 i128,
+                    as_u128_saturating u128, as_i128_saturating i128,
+                    as_u128_checked Option<u128>, as_i128_checked Option<i128>}
+            }
+            #[inline]
+            fn cast_from<AsPrimSrc: $crate::AsPrim>(x: AsPrimSrc) -> Self {
+                let from: fn($repr) -> $t = $from;
+                from(<$repr as $crate::AsPrim>::cast_from(x))
+            }
+            #[inline]
+            fn checked_cast_from<AsPrimSrc: $crate::AsPrim>(x: AsPrimSrc) -> Option<Self> {
+                let from: fn($repr) -> $t = $from;
+                let (min, max): ($repr, $repr) = ($min, $max);
+                match <$repr as $crate::AsPrim>::checked_cast_from(x) {
+                    Some(r) if min <= r && r <= max => Some(from(r)),
+                    _ => None,
+                }
+            }
+            #[inline]
+            fn saturating_cast_from<AsPrimSrc: $crate::AsPrim>(x: AsPrimSrc) -> Self {
+                let from: fn($repr) -> $t = $from;
+                let (min, max): ($repr, $repr) = ($min, $max);
+                let r = <$repr as $crate::AsPrim>::saturating_cast_from(x);
+                from(if r < min { min } else if r > max { max } else { r })
+            }
+        }
+    }
+}
+
+/// Build a lookup table of the 256 results of a function of a byte, as a
+/// `[U; 256]` constant expression.
+///
+/// The input type is `u8` or `i8`, and entry `i` holds the result for
+/// `i as u8` or `i as i8`, so that `lookup_as`, which indexes with
+/// `as_u8`, finds it. The body is the body of a `const fn`, and so has the
+/// same limits; float arithmetic in a `const fn` needs Rust 1.82.
+///
+/// ```
+/// #[macro_use] extern crate asprim;
+///
+/// use asprim::lookup_as;
+///
+/// // Expand 8-bit samples to 16 bits with gamma 2
+/// const GAMMA: [u16; 256] = cast_table!(|x: u8| -> u16 {
+///     (x as u32 * x as u32 * 65535 / (255 * 255)) as u16
+/// });
+///
+/// const SIGN: [i8; 256] = cast_table!(|x: i8| -> i8 { x.signum() });
+///
+/// # fn main() {
+/// assert_eq!(GAMMA[255], 65535);
+/// assert_eq!(lookup_as(&GAMMA, 128u8), 16512);
+/// assert_eq!(lookup_as(&SIGN, -5i8), -1);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! cast_table {
+    (|$x:ident : $in:ty| -> $out:ty $body:block) => {{
+        const fn entry($x: $in) -> $out $body
+        const TABLE: [$out; 256] = {
+            let mut table = [entry(0); 256];
+            let mut i = 0;
+            while i < 256 {
+                table[i] = entry(i as $in);
+                i += 1;
+            }
+            table
+        };
+        TABLE
+    }}
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __asprim_delegate {
+    ($t:ty, $repr:ty, $into:expr; $($method:ident $ret:ty),*) => {
+        $(
+            #[inline]
+            fn $method(self) -> $ret {
+                let into: fn($t) -> $repr = $into;
+                <$repr as $crate::AsPrim>::$method(into(self))
+            }
+        )*
+    }
+}
+
+#[cfg(feature = "i128")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __asprim_if_i128 {
+    ($($t:tt)*) => { $($t)* }
+}
+
+#[cfg(not(feature = "i128"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __asprim_if_i128 {
+    ($($t:tt)*) => {}
+}
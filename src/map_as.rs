@@ -0,0 +1,39 @@
+use AsPrim;
+
+/// Extension trait for casting the value of an `Option`.
+pub trait OptionMapAs {
+    /// Cast the contained value to the type `U` using `as`.
+    ///
+    /// ```
+    /// use asprim::OptionMapAs;
+    ///
+    /// assert_eq!(Some(3u8).map_as::<f64>(), Some(3.));
+    /// ```
+    fn map_as<U: AsPrim>(self) -> Option<U>;
+}
+
+/// Extension trait for casting the success value of a `Result`.
+pub trait ResultMapAs<E> {
+    /// Cast the success value to the type `U` using `as`.
+    ///
+    /// ```
+    /// use asprim::ResultMapAs;
+    ///
+    /// assert_eq!("300".parse::<i32>().map_as::<u8>(), Ok(44));
+    /// ```
+    fn map_as<U: AsPrim>(self) -> Result<U, E>;
+}
+
+impl<T: AsPrim> OptionMapAs for Option<T> {
+    #[inline]
+    fn map_as<U: AsPrim>(self) -> Option<U> {
+        self.map(U::cast_from)
+    }
+}
+
+impl<T: AsPrim, E> ResultMapAs<E> for Result<T, E> {
+    #[inline]
+    fn map_as<U: AsPrim>(self) -> Result<U, E> {
+        self.map(U::cast_from)
+    }
+}
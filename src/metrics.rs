@@ -0,0 +1,104 @@
+//! Counters of the casts that saturated or failed, by target type.
+//!
+//! With the `metrics` feature, the saturating casts count those that had
+//! to clamp their value, and the checked casts those that returned `None`,
+//! in a relaxed atomic counter per target type. Each cast is counted once:
+//! the per-type methods like `as_u8_checked` and `as_u8_saturating` count,
+//! and `saturating_as`, `checked_as`, `saturating_cast_from` and the rest
+//! of the crate's saturating and checked casts are built on them, including
+//! those of `analyze` and `cast_loss`. The range checks that the crate
+//! makes inside other operations, like `as_f64_exact` or the count of
+//! `saturating_cast_slice_into`, are not counted. A type made with
+//! `impl_as_prim!` is counted as its underlying primitive type, and not
+//! when only its narrower range clamps.
+//!
+//! Requires the `metrics` feature, and a target with atomic `usize`.
+//!
+//! ```
+//! use asprim::{AsPrim, PrimKind};
+//! use asprim::metrics;
+//!
+//! metrics::reset();
+//! let _ = 300.saturating_as::<u8>();
+//! let _ = (-1).checked_as::<u8>();
+//! let _ = 3.saturating_as::<u8>();
+//! let counts = metrics::snapshot();
+//! assert_eq!(counts.saturated(PrimKind::U8), 1);
+//! assert_eq!(counts.failed(PrimKind::U8), 1);
+//! assert_eq!(counts.total_saturated() + counts.total_failed(), 2);
+//! ```
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use PrimKind;
+
+const KINDS: usize = PrimKind::ALL.len();
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+static SATURATED: [AtomicUsize; KINDS] = [ZERO; KINDS];
+static FAILED: [AtomicUsize; KINDS] = [ZERO; KINDS];
+
+#[inline]
+pub(crate) fn record_saturated(kind: PrimKind) {
+    SATURATED[kind as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn record_failed(kind: PrimKind) {
+    FAILED[kind as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+/// The counts of saturated and failed casts, by target type, as returned
+/// by `snapshot`.
+///
+/// The counters are read one by one, so a snapshot taken while other
+/// threads cast may mix counts from slightly different moments.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Snapshot {
+    saturated: [usize; KINDS],
+    failed: [usize; KINDS],
+}
+
+impl Snapshot {
+    /// The number of saturating casts to `kind` that clamped their value.
+    pub fn saturated(&self, kind: PrimKind) -> usize {
+        self.saturated[kind as usize]
+    }
+
+    /// The number of checked casts to `kind` that failed.
+    pub fn failed(&self, kind: PrimKind) -> usize {
+        self.failed[kind as usize]
+    }
+
+    /// The number of saturating casts to any type that clamped their value.
+    pub fn total_saturated(&self) -> usize {
+        self.saturated.iter().fold(0, |sum, &n| sum.wrapping_add(n))
+    }
+
+    /// The number of checked casts to any type that failed.
+    pub fn total_failed(&self) -> usize {
+        self.failed.iter().fold(0, |sum, &n| sum.wrapping_add(n))
+    }
+}
+
+/// Read the counters.
+///
+/// The counters wrap around on overflow; compare snapshots with
+/// `wrapping_sub` to count the casts between them.
+pub fn snapshot() -> Snapshot {
+    let mut snapshot = Snapshot { saturated: [0; KINDS], failed: [0; KINDS] };
+    for i in 0..KINDS {
+        snapshot.saturated[i] = SATURATED[i].load(Ordering::Relaxed);
+        snapshot.failed[i] = FAILED[i].load(Ordering::Relaxed);
+    }
+    snapshot
+}
+
+/// Set all counters to zero.
+pub fn reset() {
+    for counter in SATURATED.iter().chain(&FAILED) {
+        counter.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,261 @@
+//! `ufmt` formatting of the type-erased values.
+
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+use cmp::abs;
+use {CastError, PrimKind, PrimValue};
+
+/// A float written in decimal with `digits` significant digits.
+struct Float {
+    x: f64,
+    digits: u32,
+}
+
+/// The powers of ten that scale a float to `[1, 10)`, largest first.
+const POWERS: &[(f64, i32)] = &[
+    (1e256, 256), (1e128, 128), (1e64, 64), (1e32, 32), (1e16, 16),
+    (1e8, 8), (1e4, 4), (1e2, 2), (1e1, 1),
+];
+
+impl Float {
+    fn write<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        let x = self.x;
+        if x.is_nan() {
+            return f.write_str("NaN");
+        }
+        if x.is_sign_negative() {
+            f.write_char('-')?;
+        }
+        let x = abs(x);
+        if x == f64::INFINITY {
+            return f.write_str("inf");
+        }
+        if x == 0. {
+            return f.write_str("0");
+        }
+        // Scale x to m in [1, 10), with x = m * 10^exp
+        let (mut m, mut exp) = (x, 0);
+        for &(p, k) in POWERS {
+            if m >= p {
+                m /= p;
+                exp += k;
+            }
+        }
+        for &(p, k) in POWERS {
+            if m * p < 10. {
+                m *= p;
+                exp -= k;
+            }
+        }
+        let mut scale = 1u64;
+        for _ in 1..self.digits {
+            scale *= 10;
+        }
+        let mut n = (m * scale as f64 + 0.5) as u64;
+        if n >= 10 * scale {
+            n /= 10;
+            exp += 1;
+        }
+        let mut digits = [0u8; 20];
+        let mut len = self.digits as usize;
+        for d in digits[..len].iter_mut().rev() {
+            *d = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+        while len > 1 && digits[len - 1] == b'0' {
+            len -= 1;
+        }
+        let digits = &digits[..len];
+        let digit = |d: &u8| *d as char;
+        if exp < -4 || exp >= self.digits as i32 {
+            // Scientific, like 1.5e-7
+            f.write_char(digit(&digits[0]))?;
+            if len > 1 {
+                f.write_char('.')?;
+                for d in &digits[1..] {
+                    f.write_char(digit(d))?;
+                }
+            }
+            f.write_char('e')?;
+            uDisplay::fmt(&exp, f)
+        } else if exp < 0 {
+            f.write_str("0.")?;
+            for _ in 1..-exp {
+                f.write_char('0')?;
+            }
+            for d in digits {
+                f.write_char(digit(d))?;
+            }
+            Ok(())
+        } else {
+            let int = exp as usize + 1;
+            for i in 0..int {
+                f.write_char(digits.get(i).map_or('0', digit))?;
+            }
+            if len > int {
+                f.write_char('.')?;
+                for d in &digits[int..] {
+                    f.write_char(digit(d))?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+impl uDisplay for Float {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        self.write(f)
+    }
+}
+
+impl uDebug for Float {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        self.write(f)
+    }
+}
+
+macro_rules! with_value {
+    ($value:expr, |$x:ident| $body:expr) => {
+        match $value {
+            PrimValue::U8($x) => $body,
+            PrimValue::I8($x) => $body,
+            PrimValue::U16($x) => $body,
+            PrimValue::I16($x) => $body,
+            PrimValue::U32($x) => $body,
+            PrimValue::I32($x) => $body,
+            PrimValue::U64($x) => $body,
+            PrimValue::I64($x) => $body,
+            #[cfg(feature = "i128")]
+            PrimValue::U128($x) => $body,
+            #[cfg(feature = "i128")]
+            PrimValue::I128($x) => $body,
+            PrimValue::Usize($x) => $body,
+            PrimValue::Isize($x) => $body,
+            PrimValue::F32(x) => {
+                let $x = Float { x: x as f64, digits: 7 };
+                $body
+            }
+            PrimValue::F64(x) => {
+                let $x = Float { x, digits: 15 };
+                $body
+            }
+        }
+    }
+}
+
+/// Floats are written in decimal, rounded to 7 significant digits for
+/// `f32` and 15 for `f64`, and in scientific notation if the exponent is
+/// less than -4 or not less than the number of digits, like `%g` in C:
+/// `0.1`, `1500`, `1.5e-7`. This needs no `core::fmt` float formatting.
+impl uDisplay for PrimValue {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        with_value!(*self, |x| uDisplay::fmt(&x, f))
+    }
+}
+
+/// Written like `Debug`, `U8(1)`, with floats written like the `uDisplay`
+/// implementation does.
+impl uDebug for PrimValue {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        let name = self.kind().variant_name();
+        with_value!(*self, |x| f.debug_tuple(name)?.field(&x)?.finish())
+    }
+}
+
+/// Written as the name of the type, `u8`.
+impl uDisplay for PrimKind {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        f.write_str(self.name())
+    }
+}
+
+/// Written like `Debug`, `U8`.
+impl uDebug for PrimKind {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        f.write_str(self.variant_name())
+    }
+}
+
+impl uDisplay for CastError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        let value = self.value();
+        uDisplay::fmt(&value, f)?;
+        f.write_str(" (")?;
+        uDebug::fmt(&value.kind(), f)?;
+        f.write_str(") is out of range for ")?;
+        uDebug::fmt(&self.target(), f)
+    }
+}
+
+impl uDebug for CastError {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error> {
+        f.debug_struct("CastError")?
+            .field("value", &self.value())?
+            .field("target", &self.target())?
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+    use core::str;
+
+    use super::*;
+
+    struct Buf {
+        bytes: [u8; 128],
+        len: usize,
+    }
+
+    impl uWrite for Buf {
+        type Error = Infallible;
+
+        fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+            self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+            self.len += s.len();
+            Ok(())
+        }
+    }
+
+    fn display<T: uDisplay>(x: T, expected: &str) {
+        let mut buf = Buf { bytes: [0; 128], len: 0 };
+        uDisplay::fmt(&x, &mut Formatter::new(&mut buf)).unwrap();
+        assert_eq!(str::from_utf8(&buf.bytes[..buf.len]).unwrap(), expected);
+    }
+
+    fn debug<T: uDebug>(x: T, expected: &str) {
+        let mut buf = Buf { bytes: [0; 128], len: 0 };
+        uDebug::fmt(&x, &mut Formatter::new(&mut buf)).unwrap();
+        assert_eq!(str::from_utf8(&buf.bytes[..buf.len]).unwrap(), expected);
+    }
+
+    #[test]
+    fn values() {
+        display(PrimValue::I16(-300), "-300");
+        display(PrimValue::U64(u64::MAX), "18446744073709551615");
+        display(PrimValue::F32(0.1), "0.1");
+        display(PrimValue::F64(1500.), "1500");
+        display(PrimValue::F64(-2.5e-7), "-2.5e-7");
+        display(PrimValue::F64(0.00125), "0.00125");
+        display(PrimValue::F32(16777216.), "1.677722e7");
+        display(PrimValue::F64(1e300), "1e300");
+        display(PrimValue::F64(5e-324), "4.94065645841247e-324");
+        display(PrimValue::F64(9.9999999999999999), "10");
+        display(PrimValue::F64(-0.), "-0");
+        display(PrimValue::F32(f32::NEG_INFINITY), "-inf");
+        display(PrimValue::F64(f64::NAN), "NaN");
+        debug(PrimValue::U8(7), "U8(7)");
+        debug(PrimValue::F32(0.5), "F32(0.5)");
+        display(PrimKind::Usize, "usize");
+        debug(PrimKind::Usize, "Usize");
+    }
+
+    #[test]
+    fn errors() {
+        let err = PrimValue::I32(-1).checked_cast_to(PrimKind::U8).unwrap_err();
+        display(err, "-1 (I32) is out of range for U8");
+        debug(err, "CastError { value: I32(-1), target: U8 }");
+    }
+}
@@ -0,0 +1,127 @@
+//! NumPy array-interface type strings.
+
+use bytes::Endian;
+use value::ParseKindError;
+use PrimKind;
+
+/// The type strings of each kind, little-endian and big-endian.
+const TYPESTRS: &[(PrimKind, &str, &str)] = &[
+    (PrimKind::U8, "|u1", "|u1"),
+    (PrimKind::I8, "|i1", "|i1"),
+    (PrimKind::U16, "<u2", ">u2"),
+    (PrimKind::I16, "<i2", ">i2"),
+    (PrimKind::U32, "<u4", ">u4"),
+    (PrimKind::I32, "<i4", ">i4"),
+    (PrimKind::U64, "<u8", ">u8"),
+    (PrimKind::I64, "<i8", ">i8"),
+    (PrimKind::F32, "<f4", ">f4"),
+    (PrimKind::F64, "<f8", ">f8"),
+];
+
+impl PrimKind {
+    /// The NumPy type string of the type in the byte order `endian`, like
+    /// `"<f4"`, or `None` for the 128-bit integers, which NumPy does not
+    /// have. `usize` and `isize` are the unsigned and signed integers of
+    /// the target's pointer width.
+    ///
+    /// Requires the `numpy` feature.
+    ///
+    /// ```
+    /// use asprim::{Endian, PrimKind};
+    ///
+    /// assert_eq!(PrimKind::F32.to_numpy_typestr(Endian::Little), Some("<f4"));
+    /// assert_eq!(PrimKind::I64.to_numpy_typestr(Endian::Big), Some(">i8"));
+    /// assert_eq!(PrimKind::U8.to_numpy_typestr(Endian::Big), Some("|u1"));
+    /// ```
+    pub fn to_numpy_typestr(self, endian: Endian) -> Option<&'static str> {
+        let kind = match self {
+            #[cfg(target_pointer_width = "16")]
+            PrimKind::Usize => PrimKind::U16,
+            #[cfg(target_pointer_width = "16")]
+            PrimKind::Isize => PrimKind::I16,
+            #[cfg(target_pointer_width = "32")]
+            PrimKind::Usize => PrimKind::U32,
+            #[cfg(target_pointer_width = "32")]
+            PrimKind::Isize => PrimKind::I32,
+            #[cfg(target_pointer_width = "64")]
+            PrimKind::Usize => PrimKind::U64,
+            #[cfg(target_pointer_width = "64")]
+            PrimKind::Isize => PrimKind::I64,
+            kind => kind,
+        };
+        TYPESTRS.iter().find(|t| t.0 == kind).map(|&(_, little, big)| match endian {
+            Endian::Little => little,
+            Endian::Big => big,
+        })
+    }
+
+    /// Parse a NumPy type string of a primitive numeric type, like `"<f4"`,
+    /// into its kind and byte order.
+    ///
+    /// The byte order is `<`, `>`, `=` for the native order, or `|` for
+    /// types of one byte, which also accept `<`, `>` and `=`, and give
+    /// `Endian::NATIVE`. Fixed-width integer kinds are returned, never
+    /// `Usize` or `Isize`.
+    ///
+    /// Requires the `numpy` feature.
+    ///
+    /// ```
+    /// use asprim::{Endian, PrimKind};
+    ///
+    /// assert_eq!(PrimKind::from_numpy_typestr(">u2"), Ok((PrimKind::U16, Endian::Big)));
+    /// assert_eq!(PrimKind::from_numpy_typestr("|i1"), Ok((PrimKind::I8, Endian::NATIVE)));
+    /// assert!(PrimKind::from_numpy_typestr("<c8").is_err());
+    /// assert!(PrimKind::from_numpy_typestr("|f4").is_err());
+    /// ```
+    pub fn from_numpy_typestr(s: &str) -> Result<(PrimKind, Endian), ParseKindError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 3 {
+            return Err(ParseKindError(()));
+        }
+        let endian = match bytes[0] {
+            b'<' => Some(Endian::Little),
+            b'>' => Some(Endian::Big),
+            b'=' => Some(Endian::NATIVE),
+            b'|' => None,
+            _ => return Err(ParseKindError(())),
+        };
+        let kind = TYPESTRS.iter()
+            .find(|t| t.1.as_bytes()[1..] == bytes[1..])
+            .map(|t| t.0)
+            .ok_or(ParseKindError(()))?;
+        match endian {
+            _ if kind.bits() == 8 => Ok((kind, Endian::NATIVE)),
+            Some(endian) => Ok((kind, endian)),
+            None => Err(ParseKindError(())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for &kind in PrimKind::ALL {
+            for &endian in &[Endian::Little, Endian::Big] {
+                let typestr = match kind.to_numpy_typestr(endian) {
+                    Some(t) => t,
+                    None => {
+                        assert_eq!(kind.bits(), 128);
+                        continue;
+                    }
+                };
+                let (parsed, parsed_endian) = PrimKind::from_numpy_typestr(typestr).unwrap();
+                assert_eq!(parsed.bits(), kind.bits());
+                assert_eq!(parsed.is_signed(), kind.is_signed());
+                if kind.bits() > 8 {
+                    assert_eq!(parsed_endian, endian);
+                }
+            }
+        }
+        assert_eq!(PrimKind::from_numpy_typestr("=f8"), Ok((PrimKind::F64, Endian::NATIVE)));
+        assert!(PrimKind::from_numpy_typestr("<f").is_err());
+        assert!(PrimKind::from_numpy_typestr("<f16").is_err());
+    }
+}
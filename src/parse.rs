@@ -0,0 +1,155 @@
+use core::fmt;
+
+use cmp::checked;
+use {AsPrim, PrimKind, Signed, Unsigned};
+
+/// An error from `parse_prim`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ParsePrimError {
+    /// The text is empty.
+    Empty,
+    /// The text is not a number of the target type.
+    Invalid,
+    /// The number is out of range for the target type.
+    OutOfRange,
+}
+
+impl fmt::Display for ParsePrimError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ParsePrimError::Empty => "cannot parse a number from empty text",
+            ParsePrimError::Invalid => "invalid number",
+            ParsePrimError::OutOfRange => "number out of range for the target type",
+        })
+    }
+}
+
+/// Parse a decimal number of the type `P`.
+///
+/// Integers are parsed exactly through the widest integer types, so any
+/// value in range of `P` is accepted and numbers out of range are errors;
+/// fractions and exponents are not accepted for integer types. Floats are
+/// parsed with the standard float parsers, and round to the closest value.
+///
+/// ```
+/// use asprim::{parse_prim, ParsePrimError};
+///
+/// assert_eq!(parse_prim::<u8>("255"), Ok(255));
+/// assert_eq!(parse_prim::<u8>("256"), Err(ParsePrimError::OutOfRange));
+/// assert_eq!(parse_prim::<i64>("-9223372036854775808"), Ok(i64::MIN));
+/// assert_eq!(parse_prim::<f32>("1e3"), Ok(1000.));
+/// assert_eq!(parse_prim::<u32>("1e3"), Err(ParsePrimError::Invalid));
+/// ```
+pub fn parse_prim<P: AsPrim>(s: &str) -> Result<P, ParsePrimError> {
+    if s.is_empty() {
+        return Err(ParsePrimError::Empty);
+    }
+    match P::KIND {
+        PrimKind::F32 => s.parse::<f32>().map(P::cast_from).map_err(|_| ParsePrimError::Invalid),
+        PrimKind::F64 => s.parse::<f64>().map(P::cast_from).map_err(|_| ParsePrimError::Invalid),
+        _ => parse_int(s, 10),
+    }
+}
+
+/// Parse an integer of the type `P` in the base `radix`.
+///
+/// The number may have a sign, and then the prefix of its base: `0x` for
+/// 16, `0o` for 8 or `0b` for 2. Float types are not accepted.
+///
+/// ```
+/// use asprim::{parse_prim_radix, ParsePrimError};
+///
+/// assert_eq!(parse_prim_radix::<u16>("0xffff", 16), Ok(0xffff));
+/// assert_eq!(parse_prim_radix::<u16>("ffff", 16), Ok(0xffff));
+/// assert_eq!(parse_prim_radix::<i8>("-0b10000000", 2), Ok(-128));
+/// assert_eq!(parse_prim_radix::<u8>("0x100", 16), Err(ParsePrimError::OutOfRange));
+/// assert_eq!(parse_prim_radix::<f32>("10", 10), Err(ParsePrimError::Invalid));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `radix` is not in the range 2 to 36.
+pub fn parse_prim_radix<P: AsPrim>(s: &str, radix: u32) -> Result<P, ParsePrimError> {
+    assert!((2..=36).contains(&radix), "parse_prim_radix: radix {} is not in 2..=36", radix);
+    if s.is_empty() {
+        return Err(ParsePrimError::Empty);
+    }
+    if P::KIND.is_float() {
+        return Err(ParsePrimError::Invalid);
+    }
+    parse_int(s, radix)
+}
+
+fn parse_int<P: AsPrim>(s: &str, radix: u32) -> Result<P, ParsePrimError> {
+    let negative = s.starts_with('-');
+    let unsigned = s.strip_prefix(|c| c == '+' || c == '-').unwrap_or(s);
+    let prefix = match radix {
+        16 => "0x",
+        8 => "0o",
+        2 => "0b",
+        _ => "",
+    };
+    let digits = if prefix.is_empty() {
+        unsigned
+    } else {
+        unsigned.strip_prefix(prefix).unwrap_or(unsigned)
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(ParsePrimError::Invalid);
+    }
+    // With valid digits, the only way to fail is to be out of range for
+    // the wide types too.
+    let value = if negative {
+        // Negate as m - 1, which fits when -m is the minimum.
+        Unsigned::from_str_radix(digits, radix).ok()
+            .and_then(|m| match m.checked_sub(1) {
+                None => Some(0),
+                Some(m1) => checked::<Signed, _>(m1).map(|x| -x - 1),
+            })
+            .and_then(P::checked_cast_from)
+    } else {
+        Unsigned::from_str_radix(digits, radix).ok().and_then(P::checked_cast_from)
+    };
+    value.ok_or(ParsePrimError::OutOfRange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints() {
+        assert_eq!(parse_prim::<i8>("-128"), Ok(-128));
+        assert_eq!(parse_prim::<i8>("+127"), Ok(127));
+        assert_eq!(parse_prim::<i8>("-129"), Err(ParsePrimError::OutOfRange));
+        assert_eq!(parse_prim::<u64>("-0"), Ok(0));
+        assert_eq!(parse_prim::<u64>("-1"), Err(ParsePrimError::OutOfRange));
+        assert_eq!(parse_prim::<u64>("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(parse_prim::<u64>("99999999999999999999999999999999999999999"),
+                   Err(ParsePrimError::OutOfRange));
+        assert_eq!(parse_prim::<usize>(""), Err(ParsePrimError::Empty));
+        assert_eq!(parse_prim::<usize>("-"), Err(ParsePrimError::Invalid));
+        assert_eq!(parse_prim::<usize>("1.0"), Err(ParsePrimError::Invalid));
+        assert_eq!(parse_prim::<usize>(" 1"), Err(ParsePrimError::Invalid));
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(parse_prim::<f64>("9007199254740993"), Ok(9007199254740992.));
+        assert_eq!(parse_prim::<f32>("-0.5"), Ok(-0.5));
+        assert_eq!(parse_prim::<f32>("inf"), Ok(f32::INFINITY));
+        assert_eq!(parse_prim::<f64>("x"), Err(ParsePrimError::Invalid));
+    }
+
+    #[test]
+    fn radix() {
+        assert_eq!(parse_prim_radix::<i64>("-8000000000000000", 16), Ok(i64::MIN));
+        assert_eq!(parse_prim_radix::<i64>("-8000000000000001", 16),
+                   Err(ParsePrimError::OutOfRange));
+        assert_eq!(parse_prim_radix::<u32>("0o777", 8), Ok(0o777));
+        assert_eq!(parse_prim_radix::<u32>("0x10", 10), Err(ParsePrimError::Invalid));
+        assert_eq!(parse_prim_radix::<u32>("0x", 16), Err(ParsePrimError::Invalid));
+        assert_eq!(parse_prim_radix::<u32>("zz", 36), Ok(36 * 36 - 1));
+        assert_eq!(parse_prim_radix::<i32>("-0", 2), Ok(0));
+    }
+}
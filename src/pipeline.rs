@@ -0,0 +1,134 @@
+use AsPrim;
+#[cfg(feature = "libm")]
+use {PrimFloat, RoundingMode};
+
+/// A builder for a chain of numeric steps ending in a cast.
+///
+/// The value keeps its type until a step needs arithmetic, which is done
+/// in `f64`. The steps are applied in order, and can all inline, so the
+/// chain compiles to the same code as writing it out.
+///
+/// ```
+/// use asprim::Cast;
+///
+/// // Scale a sample to 16 bits, add dither noise, and narrow
+/// let noise = 0.3;
+/// let x: i16 = Cast::of(0.75f32).scale(32768.).offset(noise).saturate().to();
+/// assert_eq!(x, 24576);
+///
+/// // Without `saturate`, the final cast is `as`
+/// assert_eq!(Cast::of(300).to::<u8>(), 44);
+/// assert_eq!(Cast::of(300).saturate().to::<u8>(), 255);
+/// assert_eq!(Cast::of(300).checked_to::<u8>(), None);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cast<T> {
+    value: T,
+    saturate: bool,
+}
+
+impl<T: AsPrim> Cast<T> {
+    /// Start with the value `x`.
+    #[inline]
+    pub fn of(x: T) -> Self {
+        Cast { value: x, saturate: false }
+    }
+
+    /// Make the final cast saturating, like `AsPrim::saturating_as`.
+    #[inline]
+    pub fn saturate(self) -> Self {
+        Cast { saturate: true, ..self }
+    }
+
+    /// Multiply the value by `factor`.
+    #[inline]
+    pub fn scale(self, factor: f64) -> Cast<f64> {
+        self.map(|x| x * factor)
+    }
+
+    /// Add `delta` to the value.
+    #[inline]
+    pub fn offset(self, delta: f64) -> Cast<f64> {
+        self.map(|x| x + delta)
+    }
+
+    /// Apply `f` to the value as `f64`.
+    #[inline]
+    pub fn map<F>(self, f: F) -> Cast<f64>
+        where F: FnOnce(f64) -> f64
+    {
+        Cast { value: f(self.value.as_f64()), saturate: self.saturate }
+    }
+
+    /// Clamp the value to the range `min` to `max`.
+    ///
+    /// NaN is left as it is.
+    #[inline]
+    pub fn clamp(self, min: T, max: T) -> Self {
+        let value = if self.value < min {
+            min
+        } else if self.value > max {
+            max
+        } else {
+            self.value
+        };
+        Cast { value, ..self }
+    }
+
+    /// Round the value to the closest integer, and ties away from zero.
+    ///
+    /// Requires the `libm` feature.
+    #[cfg(feature = "libm")]
+    #[inline]
+    pub fn round(self) -> Cast<f64> {
+        self.round_with(RoundingMode::NearestAway)
+    }
+
+    /// Round the value to an integer using `mode`.
+    ///
+    /// Requires the `libm` feature.
+    #[cfg(feature = "libm")]
+    #[inline]
+    pub fn round_with(self, mode: RoundingMode) -> Cast<f64> {
+        self.map(|x| x.round_with(mode))
+    }
+
+    /// The value before the final cast.
+    #[inline]
+    pub fn value(self) -> T {
+        self.value
+    }
+
+    /// Cast the value to the type `U`, using `as` or, after `saturate`,
+    /// clamping it to the range of `U`.
+    #[inline]
+    pub fn to<U: AsPrim>(self) -> U {
+        if self.saturate {
+            self.value.saturating_as()
+        } else {
+            self.value.as_()
+        }
+    }
+
+    /// Cast the value to the type `U`, or return `None` if it is out of
+    /// range, like `AsPrim::checked_as`.
+    #[inline]
+    pub fn checked_to<U: AsPrim>(self) -> Option<U> {
+        self.value.checked_as()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        assert_eq!(Cast::of(u64::MAX).to::<u64>(), u64::MAX);
+        assert_eq!(Cast::of(-7).clamp(-5, 5).to::<i8>(), -5);
+        assert_eq!(Cast::of(2u8).scale(-1.5).saturate().to::<u8>(), 0);
+        assert_eq!(Cast::of(2u8).map(|x| x * x).value(), 4.);
+        #[cfg(feature = "libm")]
+        assert_eq!(Cast::of(2.5f32).round().to::<i32>(), 3);
+    }
+}
@@ -0,0 +1,31 @@
+//! The traits, policies and macros of the crate, for a glob import.
+//!
+//! ```
+//! extern crate asprim;
+//!
+//! use asprim::prelude::*;
+//!
+//! const SQUARES: [u16; 256] = cast_table!(|x: u8| -> u16 { x as u16 * x as u16 });
+//!
+//! fn halve<P: PrimInt>(x: P) -> P {
+//!     (x.as_f64() / 2.).saturating_as()
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(halve(7u8), 3);
+//! assert_eq!(SQUARES[16], 256);
+//! assert_eq!(CastPolicy::Saturating.cast::<_, i8>(300), 127);
+//! # }
+//! ```
+
+pub use {AsPrim, CastFrom, CastInto, FromPrim, PrimFloat, PrimInt};
+pub use {CellAs, IteratorAs, OptionMapAs, PrimBits, PrimBytes, ResultMapAs};
+pub use {CastPolicy, NonFinite, RoundingMode};
+#[cfg(feature = "duration")]
+pub use AsDuration;
+#[cfg(feature = "portable-atomic")]
+pub use AtomicAs;
+#[cfg(feature = "funty")]
+pub use {FloatingPrim, IntegralPrim};
+
+pub use {cast_table, impl_as_prim};
@@ -0,0 +1,116 @@
+//! Conversions between Python numbers and `PrimValue`.
+//!
+//! Requires the `pyo3` feature.
+
+use pyo3::exceptions::{PyOverflowError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyFloat, PyInt};
+use pyo3::{Borrowed, IntoPyObjectExt};
+
+use {AsPrim, CastPolicy, PrimValue};
+
+/// A Python `float` becomes `F64` and an `int` becomes `I64` if it fits,
+/// else the first of `U64`, `I128` and `U128` that fits. Larger `int`s
+/// raise `OverflowError`, and other types `TypeError`.
+impl<'a, 'py> FromPyObject<'a, 'py> for PrimValue {
+    type Error = PyErr;
+
+    fn extract(obj: Borrowed<'a, 'py, PyAny>) -> PyResult<Self> {
+        if obj.is_instance_of::<PyFloat>() {
+            return Ok(PrimValue::F64(obj.extract()?));
+        }
+        if !obj.is_instance_of::<PyInt>() {
+            return Err(PyTypeError::new_err("expected an int or a float"));
+        }
+        if let Ok(x) = obj.extract::<i64>() {
+            return Ok(PrimValue::I64(x));
+        }
+        if let Ok(x) = obj.extract::<u64>() {
+            return Ok(PrimValue::U64(x));
+        }
+        #[cfg(feature = "i128")]
+        {
+            if let Ok(x) = obj.extract::<i128>() {
+                return Ok(PrimValue::I128(x));
+            }
+            if let Ok(x) = obj.extract::<u128>() {
+                return Ok(PrimValue::U128(x));
+            }
+        }
+        Err(PyOverflowError::new_err("int too large to convert"))
+    }
+}
+
+/// Floats become a Python `float`, and integers an `int`.
+impl<'py> IntoPyObject<'py> for PrimValue {
+    type Target = PyAny;
+    type Output = Bound<'py, PyAny>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        match self {
+            PrimValue::U8(x) => x.into_bound_py_any(py),
+            PrimValue::I8(x) => x.into_bound_py_any(py),
+            PrimValue::U16(x) => x.into_bound_py_any(py),
+            PrimValue::I16(x) => x.into_bound_py_any(py),
+            PrimValue::U32(x) => x.into_bound_py_any(py),
+            PrimValue::I32(x) => x.into_bound_py_any(py),
+            PrimValue::U64(x) => x.into_bound_py_any(py),
+            PrimValue::I64(x) => x.into_bound_py_any(py),
+            #[cfg(feature = "i128")]
+            PrimValue::U128(x) => x.into_bound_py_any(py),
+            #[cfg(feature = "i128")]
+            PrimValue::I128(x) => x.into_bound_py_any(py),
+            PrimValue::Usize(x) => x.into_bound_py_any(py),
+            PrimValue::Isize(x) => x.into_bound_py_any(py),
+            PrimValue::F32(x) => x.into_bound_py_any(py),
+            PrimValue::F64(x) => x.into_bound_py_any(py),
+        }
+    }
+}
+
+/// Extract a Python number as the type `T`, or raise `OverflowError` if
+/// it is out of range, like `AsPrim::checked_as`.
+///
+/// Python floats are truncated toward zero when `T` is an integer.
+pub fn extract_prim<T: AsPrim>(obj: &Bound<PyAny>) -> PyResult<T> {
+    obj.extract::<PrimValue>()?
+        .checked_as()
+        .ok_or_else(|| PyOverflowError::new_err("value out of range for the target type"))
+}
+
+/// Extract a Python number as the type `T`, casting it using `policy`.
+pub fn extract_prim_with<T: AsPrim>(obj: &Bound<PyAny>, policy: CastPolicy) -> PyResult<T> {
+    let value = obj.extract::<PrimValue>()?;
+    Ok(match policy {
+        CastPolicy::As => value.as_(),
+        CastPolicy::Saturating => value.saturating_as(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ffi::CStr;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        Python::initialize();
+        Python::attach(|py| {
+            let code = CStr::from_bytes_with_nul(b"2 ** 64\0").unwrap();
+            let big = py.eval(code, None, None).unwrap();
+            assert!(extract_prim::<u8>(&big).is_err());
+            assert_eq!(extract_prim_with::<u8>(&big, CastPolicy::Saturating).unwrap(), 255);
+            assert_eq!(extract_prim_with::<u8>(&big, CastPolicy::As).unwrap(), 0);
+            let x = 2.5f64.into_pyobject(py).unwrap().into_any();
+            assert_eq!(extract_prim::<i32>(&x).unwrap(), 2);
+            let s = "1".into_pyobject(py).unwrap().into_any();
+            assert!(s.extract::<PrimValue>().unwrap_err().is_instance_of::<PyTypeError>(py));
+            for &v in &[PrimValue::I8(-3), PrimValue::U64(u64::MAX), PrimValue::F32(0.5)] {
+                let obj = v.into_pyobject(py).unwrap();
+                assert_eq!(obj.extract::<PrimValue>().unwrap().as_::<f64>(), v.as_::<f64>());
+            }
+        });
+    }
+}
@@ -0,0 +1,96 @@
+//! `quickcheck` implementations.
+//!
+//! Requires the `quickcheck` feature.
+
+extern crate std;
+
+use self::std::boxed::Box;
+use quickcheck::{Arbitrary, Gen};
+
+use {AsPrim, PrimKind, PrimValue};
+
+impl Arbitrary for PrimKind {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(PrimKind::ALL).unwrap()
+    }
+}
+
+/// Pick one of `T::BOUNDARY_VALUES` a quarter of the time.
+fn prim<T: AsPrim + Arbitrary>(g: &mut Gen) -> PrimValue
+    where PrimValue: From<T>
+{
+    let boundary = *g.choose(&[true, false, false, false]).unwrap();
+    match g.choose(T::BOUNDARY_VALUES) {
+        Some(&x) if boundary => PrimValue::from(x),
+        _ => PrimValue::from(T::arbitrary(g)),
+    }
+}
+
+/// Shrink with the shrinker of `T`, which moves toward zero.
+fn shrink<T: AsPrim + Arbitrary>(x: T) -> Box<dyn Iterator<Item = PrimValue>>
+    where PrimValue: From<T>
+{
+    Box::new(x.shrink().map(PrimValue::from))
+}
+
+impl Arbitrary for PrimValue {
+    fn arbitrary(g: &mut Gen) -> Self {
+        match PrimKind::arbitrary(g) {
+            PrimKind::U8 => prim::<u8>(g),
+            PrimKind::I8 => prim::<i8>(g),
+            PrimKind::U16 => prim::<u16>(g),
+            PrimKind::I16 => prim::<i16>(g),
+            PrimKind::U32 => prim::<u32>(g),
+            PrimKind::I32 => prim::<i32>(g),
+            PrimKind::U64 => prim::<u64>(g),
+            PrimKind::I64 => prim::<i64>(g),
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => prim::<u128>(g),
+            #[cfg(feature = "i128")]
+            PrimKind::I128 => prim::<i128>(g),
+            PrimKind::Usize => prim::<usize>(g),
+            PrimKind::Isize => prim::<isize>(g),
+            PrimKind::F32 => prim::<f32>(g),
+            PrimKind::F64 => prim::<f64>(g),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        match *self {
+            PrimValue::U8(x) => shrink(x),
+            PrimValue::I8(x) => shrink(x),
+            PrimValue::U16(x) => shrink(x),
+            PrimValue::I16(x) => shrink(x),
+            PrimValue::U32(x) => shrink(x),
+            PrimValue::I32(x) => shrink(x),
+            PrimValue::U64(x) => shrink(x),
+            PrimValue::I64(x) => shrink(x),
+            #[cfg(feature = "i128")]
+            PrimValue::U128(x) => shrink(x),
+            #[cfg(feature = "i128")]
+            PrimValue::I128(x) => shrink(x),
+            PrimValue::Usize(x) => shrink(x),
+            PrimValue::Isize(x) => shrink(x),
+            PrimValue::F32(x) => shrink(x),
+            PrimValue::F64(x) => shrink(x),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::{Arbitrary, quickcheck};
+    use PrimValue;
+
+    quickcheck! {
+        fn shrinks_keep_kind(x: PrimValue) -> bool {
+            x.shrink().all(|y| y.kind() == x.kind())
+        }
+    }
+
+    #[test]
+    fn shrinks_toward_zero() {
+        let x = PrimValue::from(-300i32);
+        assert!(x.shrink().any(|y| y == PrimValue::I32(0)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Random values of any primitive type.
+//!
+//! Requires the `rand` feature.
+
+use rand::{Rng, RngExt};
+
+use {AsPrim, PrimKind, Unsigned};
+
+/// Return a random value of the type `T`.
+///
+/// Integers are uniform over the range of the primitive type, and floats
+/// uniform in `[0, 1)`, like `rand`'s `StandardUniform`.
+///
+/// ```
+/// extern crate asprim;
+/// extern crate rand;
+///
+/// use asprim::{random_prim, AsPrim};
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// fn noise<T: AsPrim>(n: usize) -> Vec<T> {
+///     let mut rng = SmallRng::seed_from_u64(1);
+///     (0..n).map(|_| random_prim(&mut rng)).collect()
+/// }
+///
+/// # fn main() {
+/// assert!(noise::<f32>(100).iter().all(|&x| 0. <= x && x < 1.));
+/// assert_eq!(noise::<u8>(3).len(), 3);
+/// # }
+/// ```
+pub fn random_prim<T: AsPrim, R: Rng + ?Sized>(rng: &mut R) -> T {
+    match T::KIND {
+        PrimKind::F32 => T::cast_from(rng.random::<f32>()),
+        PrimKind::F64 => T::cast_from(rng.random::<f64>()),
+        // Truncating to a narrower type keeps the distribution uniform
+        _ => T::cast_from(rng.random::<Unsigned>()),
+    }
+}
+
+/// Return a random value of the type `T` in the range from `low` to
+/// `high`, but not including `high`.
+///
+/// ***Panics*** if `low` is not less than `high`, or if either is
+/// not finite.
+///
+/// ```
+/// extern crate asprim;
+/// extern crate rand;
+///
+/// use asprim::random_range_as;
+/// use rand::SeedableRng;
+/// use rand::rngs::SmallRng;
+///
+/// # fn main() {
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// for _ in 0..100 {
+///     let x = random_range_as(&mut rng, -128i8, 127);
+///     assert!(x < 127);
+/// }
+/// # }
+/// ```
+pub fn random_range_as<T: AsPrim, R: Rng + ?Sized>(rng: &mut R, low: T, high: T) -> T {
+    assert!(low < high, "random_range_as: low must be less than high");
+    match T::KIND {
+        PrimKind::F32 => T::cast_from(rng.random_range(low.as_f32()..high.as_f32())),
+        PrimKind::F64 => T::cast_from(rng.random_range(low.as_f64()..high.as_f64())),
+        _ => {
+            // The width of the range fits in the unsigned type, even for the
+            // widest signed types, and so does the offset from `low`
+            let low = low.as_::<Unsigned>();
+            let width = high.as_::<Unsigned>().wrapping_sub(low);
+            T::cast_from(low.wrapping_add(rng.random_range(0..width)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::SmallRng;
+
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut seen = [false; 4];
+        for _ in 0..1000 {
+            let x = random_range_as(&mut rng, -2i64, 2);
+            seen[(x + 2) as usize] = true;
+            let y = random_range_as(&mut rng, i32::MIN, i32::MAX);
+            assert!(y != i32::MAX);
+            let z = random_range_as(&mut rng, -1.5f32, -1.25);
+            assert!((-1.5..-1.25).contains(&z));
+        }
+        assert_eq!(seen, [true; 4]);
+        assert_eq!(random_range_as(&mut rng, 5u8, 6), 5);
+        #[cfg(feature = "i128")]
+        assert!(random_range_as(&mut rng, i128::MIN, i128::MIN + 3) < i128::MIN + 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty() {
+        random_range_as(&mut SmallRng::seed_from_u64(0), 1u16, 1);
+    }
+}
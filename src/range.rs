@@ -0,0 +1,61 @@
+use core::ops::{Range, RangeInclusive};
+
+use {AsPrim, CastPolicy};
+
+/// Cast both endpoints of `range` to the type `U` using `policy`.
+///
+/// ```
+/// use asprim::{cast_range, CastPolicy};
+///
+/// assert_eq!(cast_range::<_, u8>(-1.5..300., CastPolicy::Saturating), 0..255);
+/// ```
+#[inline]
+pub fn cast_range<T: AsPrim, U: AsPrim>(range: Range<T>, policy: CastPolicy) -> Range<U> {
+    policy.cast(range.start)..policy.cast(range.end)
+}
+
+/// Cast both endpoints of `range` to the type `U` using `policy`.
+#[inline]
+pub fn cast_range_inclusive<T: AsPrim, U: AsPrim>(range: RangeInclusive<T>, policy: CastPolicy)
+    -> RangeInclusive<U>
+{
+    let (start, end) = range.into_inner();
+    policy.cast(start)..=policy.cast(end)
+}
+
+/// Cast both endpoints of `range` to the type `U`, or return `None` if
+/// either is out of range for `U`.
+///
+/// ```
+/// use asprim::checked_cast_range;
+///
+/// assert_eq!(checked_cast_range::<_, u8>(0..256), None);
+/// assert_eq!(checked_cast_range::<_, u8>(0..255), Some(0..255));
+/// ```
+#[inline]
+pub fn checked_cast_range<T: AsPrim, U: AsPrim>(range: Range<T>) -> Option<Range<U>> {
+    Some(range.start.checked_as()?..range.end.checked_as()?)
+}
+
+/// Cast both endpoints of `range` to the type `U`, or return `None` if
+/// either is out of range for `U`.
+#[inline]
+pub fn checked_cast_range_inclusive<T: AsPrim, U: AsPrim>(range: RangeInclusive<T>)
+    -> Option<RangeInclusive<U>>
+{
+    let (start, end) = range.into_inner();
+    Some(start.checked_as()?..=end.checked_as()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inclusive() {
+        assert_eq!(cast_range_inclusive::<_, i8>(0..=300, CastPolicy::As), 0..=44);
+        assert_eq!(cast_range_inclusive::<_, i8>(0..=300, CastPolicy::Saturating), 0..=127);
+        assert_eq!(checked_cast_range_inclusive::<_, f32>(-1..=1), Some(-1.0..=1.0));
+        assert_eq!(checked_cast_range_inclusive::<_, u32>(-1..=1), None);
+    }
+}
@@ -0,0 +1,137 @@
+use core::iter::{Product, Sum};
+use core::ops::{Add, Mul};
+
+use AsPrim;
+
+/// A sum accumulated in the primitive type `T`, from values of any
+/// primitive types.
+///
+/// The standard `Sum` only adds values of one type; wrap each item with
+/// `SumAs::from` to cast it to `T` first. `IteratorAs::sum_as` does both
+/// in one step. The additions are those of `T`, so an integer sum
+/// overflows like `Iterator::sum` does.
+///
+/// ```
+/// use asprim::SumAs;
+///
+/// let bytes = [200u8, 100, 50];
+/// let sum = bytes.iter().copied().map(SumAs::<u32>::from).sum::<SumAs<u32>>();
+/// assert_eq!(sum.0, 350);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct SumAs<T>(pub T);
+
+/// A product accumulated in the primitive type `T`, from values of any
+/// primitive types.
+///
+/// Like `SumAs`, but for `Product`; `IteratorAs::product_as` wraps the
+/// items and takes the product in one step.
+///
+/// ```
+/// use asprim::ProductAs;
+///
+/// let factors = [1000u16, 1000, 1000];
+/// let product = factors.iter().copied().map(ProductAs::<u64>::from).product::<ProductAs<u64>>();
+/// assert_eq!(product.0, 1_000_000_000);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct ProductAs<T>(pub T);
+
+impl<T: AsPrim, U: AsPrim> From<U> for SumAs<T> {
+    /// Cast `x` to `T` using `as`.
+    #[inline]
+    fn from(x: U) -> Self {
+        SumAs(T::cast_from(x))
+    }
+}
+
+impl<T: AsPrim, U: AsPrim> From<U> for ProductAs<T> {
+    /// Cast `x` to `T` using `as`.
+    #[inline]
+    fn from(x: U) -> Self {
+        ProductAs(T::cast_from(x))
+    }
+}
+
+impl<T: AsPrim + Add<Output = T>> Sum for SumAs<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        SumAs(iter.fold(T::cast_from(0u8), |acc, x| acc + x.0))
+    }
+}
+
+impl<'a, T: AsPrim + Add<Output = T>> Sum<&'a SumAs<T>> for SumAs<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<T: AsPrim + Mul<Output = T>> Product for ProductAs<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        ProductAs(iter.fold(T::cast_from(1u8), |acc, x| acc * x.0))
+    }
+}
+
+impl<'a, T: AsPrim + Mul<Output = T>> Product<&'a ProductAs<T>> for ProductAs<T> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+/// Extension trait for summing and multiplying the items of an iterator
+/// in another primitive type.
+pub trait IteratorAs: Iterator + Sized
+    where Self::Item: AsPrim
+{
+    /// Cast each item to `U` using `as`, and return their sum in `U`.
+    ///
+    /// ```
+    /// use asprim::IteratorAs;
+    ///
+    /// let samples = [i16::MAX, i16::MAX, -1];
+    /// assert_eq!(samples.iter().copied().sum_as::<i32>(), 65533);
+    /// assert_eq!(samples.iter().copied().sum_as::<f64>() / 3., 21844.333333333332);
+    /// ```
+    #[inline]
+    fn sum_as<U>(self) -> U
+        where U: AsPrim + Add<Output = U>
+    {
+        self.map(SumAs::from).sum::<SumAs<U>>().0
+    }
+
+    /// Cast each item to `U` using `as`, and return their product in `U`.
+    ///
+    /// ```
+    /// use asprim::IteratorAs;
+    ///
+    /// assert_eq!((1u8..=20).product_as::<u64>(), 2432902008176640000);
+    /// ```
+    #[inline]
+    fn product_as<U>(self) -> U
+        where U: AsPrim + Mul<Output = U>
+    {
+        self.map(ProductAs::from).product::<ProductAs<U>>().0
+    }
+}
+
+impl<I: Iterator> IteratorAs for I where I::Item: AsPrim {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduce() {
+        let xs = [u8::MAX; 4];
+        assert_eq!(xs.iter().copied().sum_as::<u16>(), 1020);
+        assert_eq!(xs.iter().copied().product_as::<u32>(), 4228250625);
+        assert_eq!([0.5f32, 2.5].iter().copied().sum_as::<i32>(), 2);
+        assert_eq!([i64::MIN, -1].iter().copied().product_as::<f64>(), 9223372036854775808.);
+        assert_eq!(core::iter::empty::<u8>().sum_as::<f32>(), 0.);
+        assert_eq!(core::iter::empty::<u8>().product_as::<i8>(), 1);
+
+        let sums = [SumAs(1.5), SumAs(2.)];
+        assert_eq!(sums.iter().sum::<SumAs<f64>>(), SumAs(3.5));
+        let products = [ProductAs(3i8), ProductAs(-4)];
+        assert_eq!(products.iter().product::<ProductAs<i8>>(), ProductAs(-12));
+    }
+}
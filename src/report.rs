@@ -0,0 +1,163 @@
+//! Per-value cast diagnostics.
+
+use core::fmt;
+
+use cmp::is_exact;
+use {AsPrim, PrimKind, PrimValue};
+
+const KINDS: usize = PrimKind::ALL.len();
+
+/// What a saturating cast did to one value, as reported by `analyze`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CastOutcome {
+    /// The value is unchanged. NaN cast to a float is exact too.
+    Exact,
+    /// Integer to integer: the value was out of range and was clamped.
+    Saturated,
+    /// Float to integer: the fractional part was dropped, or the value was
+    /// out of range or NaN.
+    Truncated,
+    /// To a float: the value was rounded, or overflowed.
+    LostPrecision,
+}
+
+/// The cast of a value to one target type, part of a `CastReport`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CastEntry {
+    target: PrimKind,
+    outcome: CastOutcome,
+    value: PrimValue,
+}
+
+impl CastEntry {
+    /// The type cast to.
+    pub fn target(&self) -> PrimKind {
+        self.target
+    }
+
+    /// How the value changed.
+    pub fn outcome(&self) -> CastOutcome {
+        self.outcome
+    }
+
+    /// The result of the cast with `saturating_as`. This is also the
+    /// result of `as`, except for out of range integers, which `as` wraps.
+    pub fn value(&self) -> PrimValue {
+        self.value
+    }
+}
+
+/// The casts of a value to every primitive type, computed by `analyze`.
+///
+/// `Display` prints one line per target.
+#[derive(Copy, Clone, Debug)]
+pub struct CastReport {
+    source: PrimValue,
+    entries: [CastEntry; KINDS],
+}
+
+impl CastReport {
+    /// The value that was analyzed.
+    pub fn source(&self) -> PrimValue {
+        self.source
+    }
+
+    /// The cast to `kind`.
+    pub fn get(&self, kind: PrimKind) -> &CastEntry {
+        &self.entries[kind as usize]
+    }
+
+    /// The casts to all kinds, in the order of `PrimKind::ALL`.
+    pub fn entries(&self) -> &[CastEntry] {
+        &self.entries
+    }
+}
+
+impl fmt::Display for CastReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} ({})", self.source, self.source.kind().name())?;
+        for entry in &self.entries {
+            writeln!(f, "  {:5} {} ({:?})", entry.target.name(), entry.value, entry.outcome)?;
+        }
+        Ok(())
+    }
+}
+
+fn entry<P: AsPrim, T: AsPrim>(x: P) -> CastEntry {
+    let r = x.saturating_as::<T>();
+    let outcome = if is_exact(x, r) {
+        CastOutcome::Exact
+    } else if T::KIND.is_float() {
+        CastOutcome::LostPrecision
+    } else if P::KIND.is_float() {
+        CastOutcome::Truncated
+    } else {
+        CastOutcome::Saturated
+    };
+    CastEntry { target: T::KIND, outcome, value: PrimValue::of(r) }
+}
+
+/// Report how `value` changes when it is cast to each primitive type.
+///
+/// ```
+/// use asprim::{analyze, CastOutcome, PrimKind, PrimValue};
+///
+/// let report = analyze(300.5f64);
+/// let u8 = report.get(PrimKind::U8);
+/// assert_eq!(u8.outcome(), CastOutcome::Truncated);
+/// assert_eq!(u8.value(), PrimValue::U8(255));
+/// assert_eq!(report.get(PrimKind::F32).outcome(), CastOutcome::Exact);
+///
+/// let report = analyze(u64::MAX);
+/// assert_eq!(report.get(PrimKind::I64).outcome(), CastOutcome::Saturated);
+/// assert_eq!(report.get(PrimKind::F64).outcome(), CastOutcome::LostPrecision);
+/// ```
+pub fn analyze<P: AsPrim>(value: P) -> CastReport {
+    let source = PrimValue::of(value);
+    let mut entries = [entry::<P, P>(value); KINDS];
+    for (slot, &kind) in entries.iter_mut().zip(PrimKind::ALL) {
+        *slot = match kind {
+            PrimKind::U8 => entry::<P, u8>(value),
+            PrimKind::I8 => entry::<P, i8>(value),
+            PrimKind::U16 => entry::<P, u16>(value),
+            PrimKind::I16 => entry::<P, i16>(value),
+            PrimKind::U32 => entry::<P, u32>(value),
+            PrimKind::I32 => entry::<P, i32>(value),
+            PrimKind::U64 => entry::<P, u64>(value),
+            PrimKind::I64 => entry::<P, i64>(value),
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => entry::<P, u128>(value),
+            #[cfg(feature = "i128")]
+            PrimKind::I128 => entry::<P, i128>(value),
+            PrimKind::Usize => entry::<P, usize>(value),
+            PrimKind::Isize => entry::<P, isize>(value),
+            PrimKind::F32 => entry::<P, f32>(value),
+            PrimKind::F64 => entry::<P, f64>(value),
+        };
+    }
+    CastReport { source, entries }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes() {
+        for &kind in PrimKind::ALL {
+            let report = analyze(7u8);
+            assert_eq!(report.get(kind).target(), kind);
+            assert_eq!(report.get(kind).outcome(), CastOutcome::Exact);
+        }
+        let report = analyze(f32::NAN);
+        assert_eq!(report.source().kind(), PrimKind::F32);
+        assert_eq!(report.get(PrimKind::F64).outcome(), CastOutcome::Exact);
+        assert_eq!(report.get(PrimKind::I32).outcome(), CastOutcome::Truncated);
+        assert_eq!(report.get(PrimKind::I32).value(), PrimValue::I32(0));
+        let report = analyze(-1i8);
+        assert_eq!(report.get(PrimKind::U16).outcome(), CastOutcome::Saturated);
+        assert_eq!(report.get(PrimKind::U16).value(), PrimValue::U16(0));
+        assert_eq!(analyze(-0.0f64).get(PrimKind::U8).outcome(), CastOutcome::Exact);
+        assert_eq!(analyze(1e300).get(PrimKind::F32).outcome(), CastOutcome::LostPrecision);
+    }
+}
@@ -0,0 +1,61 @@
+use core::cmp::Ordering;
+use core::mem;
+
+use Unsigned;
+
+/// How to round a value to one that is representable in the target type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round toward zero, like `as` does for float to integer casts.
+    TowardZero,
+    /// Round toward negative infinity (floor).
+    Down,
+    /// Round toward positive infinity (ceil).
+    Up,
+    /// Round to the closest value, and ties to the even one, like `as`
+    /// does for integer to float casts.
+    NearestEven,
+    /// Round to the closest value, and ties away from zero, like
+    /// `f64::round`.
+    NearestAway,
+}
+
+/// Round the integer with sign `negative` and magnitude `m` to `f64` using
+/// `mode`, and return it with its order relative to the exact value.
+pub(crate) fn int_to_f64(negative: bool, m: Unsigned, mode: RoundingMode) -> (f64, Ordering) {
+    let width = mem::size_of::<Unsigned>() as u32 * 8;
+    let shift = (width - m.leading_zeros()).saturating_sub(f64::MANTISSA_DIGITS);
+    let sign = if negative { -1. } else { 1. };
+    let rest = m & ((1 << shift) - 1);
+    if rest == 0 {
+        return (sign * m as f64, Ordering::Equal);
+    }
+    let mantissa = m >> shift;
+    let half = 1 << (shift - 1);
+    let up = match mode {
+        RoundingMode::TowardZero => false,
+        RoundingMode::Down => negative,
+        RoundingMode::Up => !negative,
+        RoundingMode::NearestEven => rest > half || rest == half && mantissa & 1 == 1,
+        RoundingMode::NearestAway => rest >= half,
+    };
+    // The mantissa has at most 53 bits, and the scale is a power of two,
+    // so both are exact and so is their product
+    let scale = f64::from_bits((1023 + shift as u64) << 52);
+    let x = sign * (mantissa + up as Unsigned) as f64 * scale;
+    let ord = if up { Ordering::Greater } else { Ordering::Less };
+    (x, if negative { ord.reverse() } else { ord })
+}
+
+/// Round `x` to an integer using `mode`.
+#[cfg(feature = "libm")]
+#[inline]
+pub(crate) fn round_f64(x: f64, mode: RoundingMode) -> f64 {
+    match mode {
+        RoundingMode::TowardZero => ::libm::trunc(x),
+        RoundingMode::Down => ::libm::floor(x),
+        RoundingMode::Up => ::libm::ceil(x),
+        RoundingMode::NearestEven => ::libm::rint(x),
+        RoundingMode::NearestAway => ::libm::round(x),
+    }
+}
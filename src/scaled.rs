@@ -0,0 +1,232 @@
+//! Exact conversions between floats and integers scaled by a power of ten,
+//! for `PrimFloat::to_scaled_int` and `PrimFloat::from_scaled_int`.
+
+use core::mem;
+
+use {AsPrim, PrimFloat, PrimInt, PrimKind, RoundingMode, Unsigned};
+
+/// The largest number of decimals, the largest power of ten below
+/// `u128::MAX`.
+pub(crate) const MAX_DECIMALS: u32 = 38;
+
+const LIMBS: usize = 8;
+
+/// An unsigned integer of 256 bits, in 32-bit limbs from the least
+/// significant, wide enough for a float mantissa times `10^MAX_DECIMALS`
+/// and for a 128-bit integer shifted left for division by it.
+#[derive(Copy, Clone, Default)]
+struct Wide([u32; LIMBS]);
+
+impl Wide {
+    fn new(x: Unsigned) -> Self {
+        let mut w = Wide::default();
+        for (i, limb) in w.0.iter_mut().take(mem::size_of::<Unsigned>() / 4).enumerate() {
+            *limb = (x >> (32 * i)) as u32;
+        }
+        w
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&limb| limb == 0)
+    }
+
+    fn bit_len(&self) -> u32 {
+        match self.0.iter().rposition(|&limb| limb != 0) {
+            Some(i) => 32 * i as u32 + 32 - self.0[i].leading_zeros(),
+            None => 0,
+        }
+    }
+
+    fn bit(&self, i: u32) -> bool {
+        self.0[i as usize / 32] >> (i % 32) & 1 == 1
+    }
+
+    /// The value, if it fits in `Unsigned`.
+    fn get(&self) -> Option<Unsigned> {
+        if self.bit_len() > mem::size_of::<Unsigned>() as u32 * 8 {
+            return None;
+        }
+        Some(self.0.iter().rev().fold(0, |acc, &limb| acc << 32 | limb as Unsigned))
+    }
+
+    /// Multiply by `k`; the product must fit.
+    fn mul_small(&mut self, k: u32) {
+        let mut carry = 0;
+        for limb in &mut self.0 {
+            let x = *limb as u64 * k as u64 + carry;
+            *limb = x as u32;
+            carry = x >> 32;
+        }
+        debug_assert_eq!(carry, 0);
+    }
+
+    /// Divide by `k` and return the remainder.
+    fn div_small(&mut self, k: u32) -> u32 {
+        let mut rem = 0;
+        for limb in self.0.iter_mut().rev() {
+            let x = (rem as u64) << 32 | *limb as u64;
+            *limb = (x / k as u64) as u32;
+            rem = (x % k as u64) as u32;
+        }
+        rem
+    }
+
+    /// Shift left by `s` bits; the result must fit.
+    fn shl(&mut self, s: u32) {
+        debug_assert!(self.is_zero() || self.bit_len() + s <= LIMBS as u32 * 32);
+        let (limbs, bits) = ((s / 32) as usize, s % 32);
+        for i in (0..LIMBS).rev() {
+            let hi = if i >= limbs { self.0[i - limbs] } else { 0 };
+            let lo = if i > limbs { self.0[i - limbs - 1] } else { 0 };
+            self.0[i] = if bits == 0 { hi } else { hi << bits | lo >> (32 - bits) };
+        }
+    }
+
+    /// Shift right by `s` bits, at least one, and return whether the
+    /// highest bit shifted out was set, and whether any other was.
+    fn shr(&mut self, s: u32) -> (bool, bool) {
+        if s > LIMBS as u32 * 32 {
+            let sticky = !self.is_zero();
+            *self = Wide::default();
+            return (false, sticky);
+        }
+        let half = self.bit(s - 1);
+        let sticky = (0..s - 1).any(|i| self.bit(i));
+        let (limbs, bits) = ((s / 32) as usize, s % 32);
+        for i in 0..LIMBS {
+            let lo = if i + limbs < LIMBS { self.0[i + limbs] } else { 0 };
+            let hi = if i + limbs + 1 < LIMBS { self.0[i + limbs + 1] } else { 0 };
+            self.0[i] = if bits == 0 { lo } else { lo >> bits | hi << (32 - bits) };
+        }
+        (half, sticky)
+    }
+}
+
+/// Whether `mode` rounds a magnitude with sign `negative` and last bit
+/// `odd` up, when the remainder has the half bit `half` and any lower bit
+/// set if `sticky`.
+fn rounds_up(mode: RoundingMode, negative: bool, odd: bool, half: bool, sticky: bool) -> bool {
+    match mode {
+        RoundingMode::TowardZero => false,
+        RoundingMode::Down => negative && (half || sticky),
+        RoundingMode::Up => !negative && (half || sticky),
+        RoundingMode::NearestEven => half && (sticky || odd),
+        RoundingMode::NearestAway => half,
+    }
+}
+
+pub(crate) fn to_scaled_int<F: PrimFloat, T: PrimInt>(x: F, decimals: u32, mode: RoundingMode)
+    -> Option<T>
+{
+    if !x.as_f64().is_finite() || decimals > MAX_DECIMALS {
+        return None;
+    }
+    // x * 10^d is mantissa * 5^d * 2^(exponent + d)
+    let (negative, mantissa, exponent) = x.decompose();
+    let mut w = Wide::new(mantissa.as_());
+    for _ in 0..decimals {
+        w.mul_small(5);
+    }
+    let exponent = exponent + decimals as i32;
+    let magnitude = if exponent >= 0 {
+        if !w.is_zero() && w.bit_len() + exponent as u32 > mem::size_of::<Unsigned>() as u32 * 8 {
+            return None;
+        }
+        w.shl(exponent as u32);
+        w.get()?
+    } else {
+        let (half, sticky) = w.shr(-exponent as u32);
+        let m = w.get()?;
+        if rounds_up(mode, negative, m & 1 == 1, half, sticky) {
+            m.checked_add(1)?
+        } else {
+            m
+        }
+    };
+    T::join_sign(negative, magnitude.checked_as()?)
+}
+
+pub(crate) fn from_scaled_int<F: PrimFloat, T: PrimInt>(n: T, decimals: u32, mode: RoundingMode)
+    -> Option<F>
+{
+    if decimals > MAX_DECIMALS {
+        return None;
+    }
+    let (negative, magnitude) = n.split_sign();
+    let mut w = Wide::new(magnitude.as_());
+    if w.is_zero() {
+        return F::recompose(negative, 0, 0);
+    }
+    // Scale up so that the quotient has two bits more than the mantissa;
+    // 10^d is less than 2^(4d)
+    let digits = F::KIND.mantissa_digits();
+    let scale = (digits + 2 + 4 * decimals).saturating_sub(w.bit_len() - 1);
+    w.shl(scale);
+    let mut sticky = false;
+    for _ in 0..decimals {
+        sticky |= w.div_small(10) != 0;
+    }
+    // Round to the mantissa digits, or fewer for a subnormal result
+    let min_exp = match F::KIND {
+        PrimKind::F32 => f32::MIN_EXP - f32::MANTISSA_DIGITS as i32,
+        _ => f64::MIN_EXP - f64::MANTISSA_DIGITS as i32,
+    };
+    let shift = (w.bit_len() - digits).max((min_exp + scale as i32).max(1) as u32);
+    let (half, rest) = w.shr(shift);
+    let m: u64 = w.get()?.as_();
+    let up = rounds_up(mode, negative, m & 1 == 1, half, sticky || rest);
+    F::recompose(negative, m + up as u64, shift as i32 - scale as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide() {
+        let mut w = Wide::new(u32::MAX.into());
+        w.shl(100);
+        assert_eq!(w.bit_len(), 132);
+        assert_eq!(w.get(), None);
+        assert_eq!(w.shr(99), (false, false));
+        assert_eq!(w.get(), Some(Unsigned::from(u32::MAX) << 1));
+        w.mul_small(3);
+        assert_eq!(w.div_small(10), 0);
+        assert_eq!(w.shr(2), (false, true));
+        assert_eq!(w.get(), Some(644245094));
+        assert_eq!(w.shr(300), (false, true));
+        assert!(w.is_zero());
+    }
+
+    #[test]
+    fn scaled() {
+        use RoundingMode::*;
+        assert_eq!(to_scaled_int::<f64, i64>(1.005, 2, NearestAway), Some(100));
+        assert_eq!(to_scaled_int::<f64, i64>(1.015, 2, NearestAway), Some(101));
+        assert_eq!(to_scaled_int::<f64, i64>(-0.125, 2, NearestEven), Some(-12));
+        assert_eq!(to_scaled_int::<f64, i64>(-0.125, 2, NearestAway), Some(-13));
+        assert_eq!(to_scaled_int::<f64, i64>(-0.125, 2, Down), Some(-13));
+        assert_eq!(to_scaled_int::<f64, i64>(-0.125, 2, Up), Some(-12));
+        assert_eq!(to_scaled_int::<f64, u8>(-0.001, 2, TowardZero), Some(0));
+        assert_eq!(to_scaled_int::<f64, u8>(-0.001, 2, Down), None);
+        assert_eq!(to_scaled_int::<f32, u8>(2.55, 2, Up), Some(255));
+        assert_eq!(to_scaled_int::<f32, u8>(2.57, 2, Down), None);
+        assert_eq!(to_scaled_int::<f64, i64>(9.2e16, 2, NearestEven), Some(9200000000000000000));
+        assert_eq!(to_scaled_int::<f64, i64>(9.3e16, 2, NearestEven), None);
+        assert_eq!(to_scaled_int::<f64, i64>(1e300, 0, NearestEven), None);
+        assert_eq!(to_scaled_int::<f64, i64>(1e-300, 38, Up), Some(1));
+        assert_eq!(to_scaled_int::<f64, i64>(1., 39, Up), None);
+        assert_eq!(to_scaled_int::<f64, i64>(f64::NAN, 2, Up), None);
+
+        assert_eq!(from_scaled_int::<f64, i64>(1005, 3, NearestEven), Some(1.005));
+        assert_eq!(from_scaled_int::<f64, i64>(-1, 1, NearestEven), Some(-0.1));
+        assert_eq!(from_scaled_int::<f64, i64>(1, 1, Down), Some(0.09999999999999999));
+        assert_eq!(from_scaled_int::<f64, i64>(1, 1, Up), Some(0.1));
+        assert_eq!(from_scaled_int::<f32, u64>(u64::MAX, 0, TowardZero), Some(1.8446743e19));
+        assert_eq!(from_scaled_int::<f32, i8>(0, 5, Up), Some(0.));
+        assert_eq!(from_scaled_int::<f32, i8>(1, 38, NearestEven), Some(1e-38));
+        assert_eq!(from_scaled_int::<f32, i8>(1, 38, Down), Some(f32::from_bits(0x6ce3ee)));
+        assert_eq!(from_scaled_int::<f32, i8>(1, 39, Down), None);
+        assert_eq!(from_scaled_int::<f64, u64>(123456789, 38, NearestEven), Some(1.23456789e-30));
+    }
+}
@@ -0,0 +1,143 @@
+//! Deserialize functions for primitive fields, for use with
+//! `#[serde(deserialize_with = "...")]`.
+//!
+//! They accept an integer, a float, or a string holding a number, written
+//! in any primitive type, and cast it to the field's type. This accepts
+//! `7`, `7.0` and `"7"` alike.
+//!
+//! Requires the `serde` feature.
+//!
+//! For example, a field `count: u32` with
+//! `#[serde(deserialize_with = "asprim::serde_prim::deserialize_prim_checked")]`
+//! accepts any of these:
+//!
+//! ```
+//! extern crate asprim;
+//! extern crate serde;
+//!
+//! use serde::de::IntoDeserializer;
+//! use serde::de::value::Error;
+//! use asprim::serde_prim::deserialize_prim_checked;
+//!
+//! # fn main() {
+//! let count: Result<u32, Error> = deserialize_prim_checked(7.0.into_deserializer());
+//! assert_eq!(count, Ok(7));
+//! let count: Result<u32, Error> = deserialize_prim_checked("7".into_deserializer());
+//! assert_eq!(count, Ok(7));
+//! # }
+//! ```
+
+use core::fmt;
+
+use serde::de::{Deserializer, Error, Unexpected, Visitor};
+
+use {parse_prim, AsPrim, CastPolicy, PrimValue, Signed, Unsigned};
+
+struct PrimVisitor;
+
+impl<'de> Visitor<'de> for PrimVisitor {
+    type Value = PrimValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a number, or a string holding a number")
+    }
+
+    fn visit_i64<E: Error>(self, x: i64) -> Result<PrimValue, E> {
+        Ok(PrimValue::I64(x))
+    }
+
+    fn visit_u64<E: Error>(self, x: u64) -> Result<PrimValue, E> {
+        Ok(PrimValue::U64(x))
+    }
+
+    #[cfg(feature = "i128")]
+    fn visit_i128<E: Error>(self, x: i128) -> Result<PrimValue, E> {
+        Ok(PrimValue::I128(x))
+    }
+
+    #[cfg(feature = "i128")]
+    fn visit_u128<E: Error>(self, x: u128) -> Result<PrimValue, E> {
+        Ok(PrimValue::U128(x))
+    }
+
+    fn visit_f64<E: Error>(self, x: f64) -> Result<PrimValue, E> {
+        Ok(PrimValue::F64(x))
+    }
+
+    fn visit_str<E: Error>(self, s: &str) -> Result<PrimValue, E> {
+        let s = s.trim();
+        parse_prim::<Signed>(s).map(PrimValue::from)
+            .or_else(|_| parse_prim::<Unsigned>(s).map(PrimValue::from))
+            .or_else(|_| parse_prim::<f64>(s).map(PrimValue::from))
+            .map_err(|_| E::invalid_value(Unexpected::Str(s), &self))
+    }
+}
+
+fn deserialize_value<'de, D: Deserializer<'de>>(d: D) -> Result<PrimValue, D::Error> {
+    d.deserialize_any(PrimVisitor)
+}
+
+/// Deserialize a number and cast it to `P`, or fail if it is out of range
+/// for `P`, like `AsPrim::checked_as`.
+pub fn deserialize_prim_checked<'de, D, P>(d: D) -> Result<P, D::Error>
+    where D: Deserializer<'de>,
+          P: AsPrim,
+{
+    let value = deserialize_value(d)?;
+    value.checked_as().ok_or_else(|| {
+        D::Error::custom(format_args!("{} is out of range for {:?}", value, P::KIND))
+    })
+}
+
+/// Deserialize a number and cast it to `P`, clamping it to the range of
+/// `P`, like `AsPrim::saturating_as`.
+pub fn deserialize_prim_lossy<'de, D, P>(d: D) -> Result<P, D::Error>
+    where D: Deserializer<'de>,
+          P: AsPrim,
+{
+    deserialize_prim_with(d, CastPolicy::Saturating)
+}
+
+/// Deserialize a number and cast it to `P` using `policy`.
+pub fn deserialize_prim_with<'de, D, P>(d: D, policy: CastPolicy) -> Result<P, D::Error>
+    where D: Deserializer<'de>,
+          P: AsPrim,
+{
+    let value = deserialize_value(d)?;
+    Ok(match policy {
+        CastPolicy::As => value.as_(),
+        CastPolicy::Saturating => value.saturating_as(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::de::IntoDeserializer;
+    use serde::de::value::Error;
+
+    use super::*;
+
+    fn checked<'a, P: AsPrim, T: IntoDeserializer<'a, Error>>(x: T) -> Result<P, Error> {
+        deserialize_prim_checked(x.into_deserializer())
+    }
+
+    fn lossy<'a, P: AsPrim, T: IntoDeserializer<'a, Error>>(x: T) -> Result<P, Error> {
+        deserialize_prim_lossy(x.into_deserializer())
+    }
+
+    #[test]
+    fn forms() {
+        assert_eq!(checked::<u32, _>(7i64), Ok(7));
+        assert_eq!(checked::<u32, _>(7.0f64), Ok(7));
+        assert_eq!(checked::<u32, _>(" 7 "), Ok(7));
+        assert_eq!(checked::<f32, _>("-2.5"), Ok(-2.5));
+        assert_eq!(checked::<u64, _>("18446744073709551615"), Ok(u64::MAX));
+        assert!(checked::<u8, _>(-1i64).is_err());
+        assert!(checked::<u8, _>("seven").is_err());
+        assert_eq!(lossy::<u8, _>(-1i64), Ok(0));
+        assert_eq!(lossy::<u8, _>("1e3"), Ok(255));
+        let wrapped: Result<i8, Error> =
+            deserialize_prim_with(200u64.into_deserializer(), CastPolicy::As);
+        assert_eq!(wrapped, Ok(-56));
+    }
+}
@@ -0,0 +1,112 @@
+use core::fmt;
+
+use cmp::abs;
+use AsPrim;
+
+/// The SI prefixes from 10^-24 to 10^24, and their powers of ten.
+const PREFIXES: [(&str, f64); 17] = [
+    ("y", 1e-24), ("z", 1e-21), ("a", 1e-18), ("f", 1e-15), ("p", 1e-12), ("n", 1e-9),
+    ("µ", 1e-6), ("m", 1e-3), ("", 1.), ("k", 1e3), ("M", 1e6), ("G", 1e9), ("T", 1e12),
+    ("P", 1e15), ("E", 1e18), ("Z", 1e21), ("Y", 1e24),
+];
+
+/// The index of the empty prefix in `PREFIXES`.
+const UNIT: usize = 8;
+
+/// Write `x` to `w` scaled to the range 1 to 1000 with an SI prefix, with
+/// `precision` digits after the decimal point, like `"12.4 k"` or
+/// `"3.1 µ"`.
+///
+/// A space separates the number and the prefix, and is written without a
+/// prefix too, so that a unit can follow: `"12.0 "`. Values beyond the
+/// largest and smallest prefixes, yotta and yocto, keep those. Zero, NaN
+/// and the infinities have no prefix.
+///
+/// ```
+/// use asprim::format_si;
+///
+/// let mut s = String::new();
+/// format_si(12_400u32, 1, &mut s).unwrap();
+/// s.push_str("Hz, ");
+/// format_si(3.14e-6f64, 1, &mut s).unwrap();
+/// s.push_str("F, ");
+/// format_si(-0.5f32, 2, &mut s).unwrap();
+/// s.push_str("V, ");
+/// format_si(999.96, 1, &mut s).unwrap();
+/// s.push_str("W");
+/// assert_eq!(s, "12.4 kHz, 3.1 µF, -500.00 mV, 1.0 kW");
+/// ```
+pub fn format_si<P: AsPrim, W: fmt::Write + ?Sized>(x: P, precision: usize, w: &mut W)
+    -> fmt::Result
+{
+    let x = x.as_f64();
+    let mut i = UNIT;
+    if x.is_finite() && x != 0. {
+        let magnitude = abs(x);
+        while i > 0 && magnitude < PREFIXES[i].1 {
+            i -= 1;
+        }
+        while i < PREFIXES.len() - 1 && magnitude >= PREFIXES[i + 1].1 {
+            i += 1;
+        }
+        // Move up a prefix if the value rounds up to 1000
+        let mut half_step = 0.5;
+        for _ in 0..precision.min(20) {
+            half_step /= 10.;
+        }
+        if i < PREFIXES.len() - 1 && magnitude / PREFIXES[i].1 >= 1000. - half_step {
+            i += 1;
+        }
+    }
+    let (prefix, scale) = PREFIXES[i];
+    write!(w, "{:.*} {}", precision, x / scale, prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::{self, Write};
+    use core::str;
+
+    use super::*;
+
+    struct Buf {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Write for Buf {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    fn si<P: AsPrim>(x: P, precision: usize) -> Buf {
+        let mut buf = Buf { bytes: [0; 64], len: 0 };
+        format_si(x, precision, &mut buf).unwrap();
+        buf
+    }
+
+    fn check<P: AsPrim>(x: P, precision: usize, expected: &str) {
+        let buf = si(x, precision);
+        assert_eq!(str::from_utf8(&buf.bytes[..buf.len]).unwrap(), expected);
+    }
+
+    #[test]
+    fn prefixes() {
+        check(0u8, 1, "0.0 ");
+        check(1u8, 0, "1 ");
+        check(999u16, 0, "999 ");
+        check(1000u16, 1, "1.0 k");
+        check(u64::MAX, 2, "18.45 E");
+        check(-2.5e-10f64, 1, "-250.0 p");
+        check(1e27, 0, "1000 Y");
+        check(5e-30, 1, "0.0 y");
+        check(0.00099996, 2, "999.96 µ");
+        check(0.00099996, 1, "1.0 m");
+        check(f64::NAN, 1, "NaN ");
+        check(f32::NEG_INFINITY, 1, "-inf ");
+    }
+}
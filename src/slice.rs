@@ -0,0 +1,378 @@
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::slice;
+
+use cmp::{fits, is_exact};
+use {AsPrim, PrimFloat, PrimKind, PrimValue};
+
+/// Cast each element of `src` to the type `U` using `as`, and write it to
+/// the same position in `dst`.
+///
+/// ***Panics*** if the slices have different lengths.
+///
+/// ```
+/// use asprim::cast_slice_into;
+///
+/// let mut dst = [0u8; 3];
+/// cast_slice_into(&[1., 2.5, 300.], &mut dst);
+/// assert_eq!(dst, [1, 2, 255]);
+/// ```
+#[inline]
+pub fn cast_slice_into<T: AsPrim, U: AsPrim>(src: &[T], dst: &mut [U]) {
+    assert_eq!(src.len(), dst.len(), "cast_slice_into: slices have different lengths");
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s.as_();
+    }
+}
+
+/// The error of `try_cast_slice_into`: the first element that is out of
+/// range for the target type.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BulkCastError {
+    index: usize,
+    value: PrimValue,
+    target: PrimKind,
+}
+
+impl BulkCastError {
+    /// The position of the element in the source slice.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The value that did not fit.
+    pub fn value(&self) -> PrimValue {
+        self.value
+    }
+
+    /// The type it did not fit.
+    pub fn target(&self) -> PrimKind {
+        self.target
+    }
+}
+
+impl fmt::Display for BulkCastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "element {}: {} ({:?}) is out of range for {:?}", self.index, self.value,
+               self.value.kind(), self.target)
+    }
+}
+
+/// Cast each element of `src` to the type `U`, like `checked_as`, and write
+/// it to the same position in `dst`, or return an error for the first
+/// element that is out of range.
+///
+/// On error, the elements of `dst` before the failing index are written,
+/// and the rest are unchanged.
+///
+/// ***Panics*** if the slices have different lengths.
+///
+/// ```
+/// use asprim::{try_cast_slice_into, PrimValue};
+///
+/// let mut dst = [0u8; 4];
+/// assert!(try_cast_slice_into(&[1, 2, 3, 4], &mut dst).is_ok());
+/// assert_eq!(dst, [1, 2, 3, 4]);
+///
+/// let err = try_cast_slice_into(&[5, 6, 300, -1], &mut dst).unwrap_err();
+/// assert_eq!(err.index(), 2);
+/// assert_eq!(err.value(), PrimValue::I32(300));
+/// assert_eq!(dst, [5, 6, 3, 4]);
+/// ```
+pub fn try_cast_slice_into<T: AsPrim, U: AsPrim>(src: &[T], dst: &mut [U])
+    -> Result<(), BulkCastError>
+{
+    assert_eq!(src.len(), dst.len(), "try_cast_slice_into: slices have different lengths");
+    for (index, (d, &s)) in dst.iter_mut().zip(src).enumerate() {
+        match s.checked_as() {
+            Some(x) => *d = x,
+            None => {
+                return Err(BulkCastError { index, value: PrimValue::of(s), target: U::KIND });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Cast each element of `src` to the type `U`, like `saturating_as`, and
+/// write it to the same position in `dst`. Return the number of elements
+/// that were out of range and clamped; NaN cast to an integer counts too.
+///
+/// ***Panics*** if the slices have different lengths.
+///
+/// ```
+/// use asprim::saturating_cast_slice_into;
+///
+/// let mut dst = [0i8; 4];
+/// assert_eq!(saturating_cast_slice_into(&[-1000., 2.5, 1000., f64::NAN], &mut dst), 3);
+/// assert_eq!(dst, [-128, 2, 127, 0]);
+/// ```
+pub fn saturating_cast_slice_into<T: AsPrim, U: AsPrim>(src: &[T], dst: &mut [U]) -> usize {
+    assert_eq!(src.len(), dst.len(), "saturating_cast_slice_into: slices have different lengths");
+    let mut clamped = 0;
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s.saturating_as();
+        // Out of range for `U`, or for the narrower range of a type made
+        // with `impl_as_prim!`
+        if !fits::<U, T>(s) || !is_exact(U::cast_from(s), *d) {
+            clamped += 1;
+        }
+    }
+    clamped
+}
+
+/// Cast each element of `src` to the type `U` using `as`, and write it to
+/// the same position in the uninitialized buffer `dst`.
+///
+/// Return `dst` as an initialized slice. This avoids filling the buffer
+/// before the conversion.
+///
+/// ***Panics*** if the slices have different lengths.
+///
+/// ```
+/// use std::mem::MaybeUninit;
+/// use asprim::cast_slice_into_uninit;
+///
+/// let src = [1i32, -1, 70000];
+/// let mut buf: Vec<u16> = Vec::with_capacity(src.len());
+/// let dst = unsafe {
+///     std::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut MaybeUninit<u16>, src.len())
+/// };
+/// assert_eq!(cast_slice_into_uninit(&src, dst), [1, 65535, 4464]);
+/// unsafe { buf.set_len(src.len()); }
+/// ```
+#[inline]
+pub fn cast_slice_into_uninit<'a, T: AsPrim, U: AsPrim>(src: &[T], dst: &'a mut [MaybeUninit<U>])
+    -> &'a mut [U]
+{
+    assert_eq!(src.len(), dst.len(), "cast_slice_into_uninit: slices have different lengths");
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = MaybeUninit::new(s.as_());
+    }
+    // Safety: every element was written above, and MaybeUninit<U> has the
+    // same layout as U.
+    unsafe {
+        slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut U, dst.len())
+    }
+}
+
+/// Split the interleaved frames of `src` into one slice per channel,
+/// casting each element to the type `U` using `as`, in one pass.
+///
+/// `src` holds `dst.len()` channels, one element of each per frame, and
+/// each channel slice in `dst` has one element per frame.
+///
+/// ***Panics*** if there are no channels, or if the lengths do not match.
+///
+/// ```
+/// use asprim::deinterleave_cast_into;
+///
+/// let stereo = [1i16, -1, 2, -2, 3, -3];
+/// let (mut left, mut right) = ([0f32; 3], [0f32; 3]);
+/// deinterleave_cast_into(&stereo, &mut [&mut left, &mut right]);
+/// assert_eq!(left, [1., 2., 3.]);
+/// assert_eq!(right, [-1., -2., -3.]);
+/// ```
+pub fn deinterleave_cast_into<T: AsPrim, U: AsPrim>(src: &[T], dst: &mut [&mut [U]]) {
+    check_interleaved(src.len(), dst.len(), dst.iter().map(|c| c.len()),
+                      "deinterleave_cast_into");
+    for (frame, xs) in src.chunks_exact(dst.len()).enumerate() {
+        for (channel, &x) in dst.iter_mut().zip(xs) {
+            channel[frame] = x.as_();
+        }
+    }
+}
+
+/// Interleave the channel slices of `src` into frames in `dst`, casting
+/// each element to the type `U` using `as`, in one pass. This is the
+/// inverse of `deinterleave_cast_into`.
+///
+/// ***Panics*** if there are no channels, or if the lengths do not match.
+///
+/// ```
+/// use asprim::interleave_cast_into;
+///
+/// let left = [0.5f32, 1.];
+/// let right = [-0.5f32, -1.];
+/// let mut stereo = [0i16; 4];
+/// interleave_cast_into(&[&left, &right], &mut stereo);
+/// assert_eq!(stereo, [0, 0, 1, -1]);
+/// ```
+pub fn interleave_cast_into<T: AsPrim, U: AsPrim>(src: &[&[T]], dst: &mut [U]) {
+    check_interleaved(dst.len(), src.len(), src.iter().map(|c| c.len()),
+                      "interleave_cast_into");
+    for (frame, xs) in dst.chunks_exact_mut(src.len()).enumerate() {
+        for (x, channel) in xs.iter_mut().zip(src) {
+            *x = channel[frame].as_();
+        }
+    }
+}
+
+/// Cast every `src_stride`-th element of `src`, starting with the first,
+/// to the type `U` using `as`, into every `dst_stride`-th element of
+/// `dst`, starting with the first. The elements in between are not read
+/// or written.
+///
+/// Slice from the first element of a column to convert a column of
+/// interleaved or row-major data. Both slices must have the same number of
+/// strided elements, their length divided by the stride, rounded up.
+///
+/// ***Panics*** if a stride is zero, or if the numbers of strided elements
+/// differ.
+///
+/// ```
+/// use asprim::cast_strided;
+///
+/// // Convert the second column of a 3 x 3 matrix into a row
+/// let matrix = [1u8, 2, 3, 4, 5, 6, 7, 8, 9];
+/// let mut row = [0f32; 3];
+/// cast_strided(&matrix[1..], 3, &mut row, 1);
+/// assert_eq!(row, [2., 5., 8.]);
+///
+/// // Write it back into the third column of a 3 x 3 matrix
+/// let mut out = [0i64; 9];
+/// cast_strided(&row, 1, &mut out[2..], 3);
+/// assert_eq!(out, [0, 0, 2, 0, 0, 5, 0, 0, 8]);
+/// ```
+pub fn cast_strided<T: AsPrim, U: AsPrim>(src: &[T], src_stride: usize, dst: &mut [U],
+                                          dst_stride: usize)
+{
+    assert!(src_stride != 0 && dst_stride != 0, "cast_strided: stride is zero");
+    // The number of strided elements, rounding up
+    let count = |len: usize, stride: usize| len / stride + (len % stride != 0) as usize;
+    assert_eq!(count(src.len(), src_stride), count(dst.len(), dst_stride),
+               "cast_strided: slices have different numbers of strided elements");
+    for (d, s) in dst.iter_mut().step_by(dst_stride).zip(src.iter().step_by(src_stride)) {
+        *d = s.as_();
+    }
+}
+
+/// Check that interleaved data of length `len` and its channels have the
+/// same number of frames.
+fn check_interleaved<I>(len: usize, channels: usize, channel_lens: I, name: &str)
+    where I: IntoIterator<Item = usize>
+{
+    assert!(channels != 0, "{}: no channels", name);
+    let frames = len / channels;
+    assert_eq!(frames * channels, len, "{}: length is not a multiple of the channels", name);
+    for channel_len in channel_lens {
+        assert_eq!(channel_len, frames, "{}: channel length is not the number of frames", name);
+    }
+}
+
+/// View a slice of floats as their bit patterns, like `to_bits`, without
+/// copying.
+///
+/// ```
+/// use asprim::as_bits_slice;
+///
+/// assert_eq!(as_bits_slice(&[1.0f32, -0.0]), [0x3f80_0000, 0x8000_0000]);
+/// ```
+#[inline]
+pub fn as_bits_slice<F: PrimFloat>(xs: &[F]) -> &[F::Bits] {
+    // Safety: PrimFloat is sealed, and for f32 and f64, F::Bits has the
+    // same size and alignment as F, and every bit pattern is a valid integer.
+    unsafe {
+        slice::from_raw_parts(xs.as_ptr() as *const F::Bits, xs.len())
+    }
+}
+
+/// View a slice of bit patterns as floats, like `from_bits`, without
+/// copying.
+///
+/// ```
+/// use asprim::from_bits_slice;
+///
+/// assert_eq!(from_bits_slice::<f64>(&[0x4000_0000_0000_0000]), [2.0]);
+/// ```
+#[inline]
+pub fn from_bits_slice<F: PrimFloat>(bits: &[F::Bits]) -> &[F] {
+    // Safety: PrimFloat is sealed, and f32 and f64 have the same size and
+    // alignment as their F::Bits, and every bit pattern is a valid float.
+    unsafe {
+        slice::from_raw_parts(bits.as_ptr() as *const F, bits.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uninit() {
+        let mut buf = [MaybeUninit::<f32>::uninit(); 4];
+        let dst = cast_slice_into_uninit(&[0u8, 1, 2, 255], &mut buf);
+        assert_eq!(dst, [0., 1., 2., 255.]);
+        assert!(cast_slice_into_uninit::<u8, f32>(&[], &mut []).is_empty());
+    }
+
+    #[test]
+    fn bits() {
+        let xs = [0.5f32, f32::INFINITY, -1e-40];
+        let bits = as_bits_slice(&xs);
+        for (&x, &b) in xs.iter().zip(bits) {
+            assert_eq!(x.to_bits(), b);
+        }
+        assert_eq!(from_bits_slice::<f32>(bits), xs);
+        assert!(from_bits_slice::<f64>(&[!0])[0].is_nan());
+    }
+
+    #[test]
+    fn checked_and_saturating() {
+        let mut dst = [0u16; 3];
+        let err = try_cast_slice_into(&[f32::NAN, 1., 2.], &mut dst).unwrap_err();
+        assert_eq!((err.index(), err.target()), (0, PrimKind::U16));
+        assert_eq!(dst, [0, 0, 0]);
+        assert_eq!(try_cast_slice_into(&[65535.9f64, 0., 1.], &mut dst), Ok(()));
+        assert_eq!(dst, [65535, 0, 1]);
+        assert_eq!(saturating_cast_slice_into(&[-1i64, 1 << 20, 7], &mut dst), 2);
+        assert_eq!(dst, [0, 65535, 7]);
+        let mut dst = [0f32; 2];
+        assert_eq!(saturating_cast_slice_into(&[1e300, f64::INFINITY], &mut dst), 1);
+        assert_eq!(dst, [f32::MAX, f32::INFINITY]);
+    }
+
+    #[test]
+    fn strided() {
+        let mut dst = [0u16; 5];
+        cast_strided(&[1i32, -1, 2, -1], 2, &mut dst, 4);
+        assert_eq!(dst, [1, 0, 0, 0, 2]);
+        cast_strided::<u8, u16>(&[], 3, &mut [], 1);
+        let mut dst = [0f64; 2];
+        cast_strided(&[7u8; 4], 3, &mut dst, 1);
+        assert_eq!(dst, [7., 7.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different numbers of strided elements")]
+    fn strided_mismatch() {
+        cast_strided(&[0u8; 4], 2, &mut [0u8; 4], 1);
+    }
+
+    #[test]
+    fn interleaved() {
+        let frames = [1u8, 2, 3, 4, 5, 6];
+        let (mut a, mut b, mut c) = ([0i32; 2], [0i32; 2], [0i32; 2]);
+        deinterleave_cast_into(&frames, &mut [&mut a, &mut b, &mut c]);
+        assert_eq!((a, b, c), ([1, 4], [2, 5], [3, 6]));
+        let mut back = [0u8; 6];
+        interleave_cast_into(&[&a, &b, &c], &mut back);
+        assert_eq!(back, frames);
+        let mut mono = [0f64; 6];
+        deinterleave_cast_into(&frames, &mut [&mut mono]);
+        assert_eq!(mono[5], 6.);
+        interleave_cast_into::<u8, u8>(&[&[], &[]], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn interleaved_mismatch() {
+        deinterleave_cast_into(&[1, 2, 3], &mut [&mut [0u8; 2], &mut [0u8; 1]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn length_mismatch() {
+        cast_slice_into(&[1, 2], &mut [0u8; 3]);
+    }
+}
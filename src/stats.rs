@@ -0,0 +1,211 @@
+use core::iter::FromIterator;
+
+use {AsPrim, Float};
+
+macro_rules! summary {
+    ($(#[$attr:meta])* $name:ident, $float:ty) => {
+        $(#[$attr])*
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        pub struct $name<P> {
+            count: u64,
+            min: Option<P>,
+            max: Option<P>,
+            mean: $float,
+            m2: $float,
+        }
+
+        impl<P: AsPrim> $name<P> {
+            /// Create an empty summary.
+            #[inline]
+            pub fn new() -> Self {
+                $name { count: 0, min: None, max: None, mean: 0., m2: 0. }
+            }
+
+            /// Add `x` to the summary.
+            #[inline]
+            pub fn push(&mut self, x: P) {
+                // A NaN minimum or maximum is replaced by the next value
+                match self.min {
+                    Some(min) if !(x < min || min.as_f64().is_nan()) => {}
+                    _ => self.min = Some(x),
+                }
+                match self.max {
+                    Some(max) if !(x > max || max.as_f64().is_nan()) => {}
+                    _ => self.max = Some(x),
+                }
+                self.count += 1;
+                let x = x.as_::<$float>();
+                let delta = x - self.mean;
+                self.mean += delta / self.count as $float;
+                self.m2 += delta * (x - self.mean);
+            }
+
+            /// The number of values.
+            #[inline]
+            pub fn count(&self) -> u64 {
+                self.count
+            }
+
+            /// The smallest value, or `None` if there are none.
+            #[inline]
+            pub fn min(&self) -> Option<P> {
+                self.min
+            }
+
+            /// The largest value, or `None` if there are none.
+            #[inline]
+            pub fn max(&self) -> Option<P> {
+                self.max
+            }
+
+            /// The mean of the values, or `None` if there are none.
+            #[inline]
+            pub fn mean(&self) -> Option<f64> {
+                if self.count == 0 {
+                    None
+                } else {
+                    Some(self.mean.as_f64())
+                }
+            }
+
+            /// The population variance of the values, the mean squared distance
+            /// from the mean, or `None` if there are none.
+            #[inline]
+            pub fn variance(&self) -> Option<f64> {
+                if self.count == 0 {
+                    None
+                } else {
+                    Some((self.m2 / self.count as $float).as_f64())
+                }
+            }
+
+            /// The sample variance of the values, with Bessel's correction, or
+            /// `None` if there are fewer than two.
+            #[inline]
+            pub fn sample_variance(&self) -> Option<f64> {
+                if self.count < 2 {
+                    None
+                } else {
+                    Some((self.m2 / (self.count - 1) as $float).as_f64())
+                }
+            }
+        }
+
+        impl<P: AsPrim> Default for $name<P> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<P: AsPrim> Extend<P> for $name<P> {
+            fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+                for x in iter {
+                    self.push(x);
+                }
+            }
+        }
+
+        impl<P: AsPrim> FromIterator<P> for $name<P> {
+            fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+                let mut summary = Self::new();
+                summary.extend(iter);
+                summary
+            }
+        }
+    }
+}
+
+summary!{
+    /// The count, minimum, maximum, mean and variance of values of the type
+    /// `P`, computed in one pass with Welford's algorithm in `f64`, or in
+    /// `f32` with the `prefer-f32` feature; `Summary32` always uses `f32`.
+    ///
+    /// NaN values make the mean and variance NaN, but are skipped by the
+    /// minimum and maximum unless all values are NaN.
+    ///
+    /// ```
+    /// use asprim::{summarize, Summary};
+    ///
+    /// let summary = summarize(&[2u8, 4, 4, 4, 5, 5, 7, 9]);
+    /// assert_eq!(summary.count(), 8);
+    /// assert_eq!(summary.min(), Some(2));
+    /// assert_eq!(summary.max(), Some(9));
+    /// assert_eq!(summary.mean(), Some(5.));
+    /// assert_eq!(summary.variance(), Some(4.));
+    ///
+    /// let mut summary = Summary::new();
+    /// summary.push(-1.5f32);
+    /// assert_eq!(summary.sample_variance(), None);
+    /// ```
+    Summary, Float
+}
+
+summary!{
+    /// The count, minimum, maximum, mean and variance of values of the type
+    /// `P`, like `Summary`, but always computed in `f32`.
+    ///
+    /// For targets with only a single precision FPU. Unlike the
+    /// `prefer-f32` feature, it does not change `Summary` for the other
+    /// crates that use it.
+    ///
+    /// ```
+    /// use asprim::Summary32;
+    ///
+    /// let summary: Summary32<u16> = [1u16, 2, 3, 4].iter().copied().collect();
+    /// assert_eq!(summary.mean(), Some(2.5));
+    /// assert_eq!(summary.sample_variance(), Some(1.6666666269302368));
+    /// ```
+    Summary32, f32
+}
+
+/// Summarize the values of `data` in one pass; see `Summary`.
+pub fn summarize<P: AsPrim>(data: &[P]) -> Summary<P> {
+    data.iter().copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn welford() {
+        let empty = summarize::<i32>(&[]);
+        assert_eq!(empty.count(), 0);
+        assert_eq!(empty.min(), None);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.variance(), None);
+
+        // A large offset cancels catastrophically in the naive formula
+        let offset = if cfg!(feature = "prefer-f32") { 1e4 } else { 1e9 };
+        let data = [offset + 4., offset + 7., offset + 13., offset + 16.];
+        let summary = summarize(&data);
+        assert_eq!(summary.mean(), Some(offset + 10.));
+        assert_eq!(summary.sample_variance(), Some(30.));
+        assert_eq!(summary.min(), Some(offset + 4.));
+
+        let summary = summarize(&[f32::NAN, 1., -2., f32::NAN]);
+        assert_eq!(summary.min(), Some(-2.));
+        assert_eq!(summary.max(), Some(1.));
+        assert!(summary.mean().unwrap().is_nan());
+        assert!(summarize(&[f64::NAN]).min().unwrap().is_nan());
+
+        let summary = summarize(&[i64::MIN, i64::MAX]);
+        assert_eq!(summary.min(), Some(i64::MIN));
+        assert_eq!(summary.mean(), Some(0.));
+    }
+
+    #[test]
+    fn single() {
+        let data = [1e4 + 4., 1e4 + 7., 1e4 + 13., 1e4 + 16.];
+        let summary = data.iter().copied().collect::<Summary32<f64>>();
+        assert_eq!(summary.mean(), Some(1e4 + 10.));
+        assert_eq!(summary.sample_variance(), Some(30.));
+        assert_eq!(summary.max(), Some(1e4 + 16.));
+
+        // Only f64 tells the values apart
+        let summary = [1. + 1e-9, 1. - 1e-9].iter().copied().collect::<Summary32<f64>>();
+        assert_eq!(summary.variance(), Some(0.));
+        #[cfg(not(feature = "prefer-f32"))]
+        assert!(summarize(&[1. + 1e-9, 1. - 1e-9]).variance().unwrap() > 0.);
+    }
+}
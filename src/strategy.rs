@@ -0,0 +1,88 @@
+//! `proptest` strategies for primitive values.
+//!
+//! Requires the `proptest` feature.
+
+use proptest::prelude::*;
+use proptest::arbitrary::Arbitrary;
+use proptest::sample::select;
+use proptest::strategy::Union;
+
+use {AsPrim, PrimValue};
+
+/// Generate values of `T`, a quarter of them picked from
+/// `T::BOUNDARY_VALUES`.
+///
+/// ```
+/// #[macro_use] extern crate proptest;
+/// extern crate asprim;
+///
+/// use asprim::AsPrim;
+/// use asprim::strategy::any_prim;
+///
+/// # fn main() {
+/// proptest!(|(x in any_prim::<i64>())| {
+///     if let Some(f) = x.as_f64_exact() {
+///         prop_assert_eq!(f.checked_as::<i64>(), Some(x));
+///     }
+/// });
+/// # }
+/// ```
+pub fn any_prim<T>() -> BoxedStrategy<T>
+    where T: AsPrim + Arbitrary
+{
+    if T::BOUNDARY_VALUES.is_empty() {
+        any::<T>().boxed()
+    } else {
+        prop_oneof![
+            1 => select(T::BOUNDARY_VALUES),
+            3 => any::<T>(),
+        ].boxed()
+    }
+}
+
+/// Generate values of any primitive type, using `any_prim` for each type.
+pub fn any_prim_value() -> BoxedStrategy<PrimValue> {
+    Union::new(strategies().iter().cloned().chain(strategies_128().iter().cloned())).boxed()
+}
+
+fn value<T>() -> BoxedStrategy<PrimValue>
+    where T: AsPrim + Arbitrary + Into<PrimValue>
+{
+    any_prim::<T>().prop_map(Into::into).boxed()
+}
+
+fn strategies() -> [BoxedStrategy<PrimValue>; 12] {
+    [value::<u8>(), value::<i8>(), value::<u16>(), value::<i16>(),
+     value::<u32>(), value::<i32>(), value::<u64>(), value::<i64>(),
+     value::<usize>(), value::<isize>(), value::<f32>(), value::<f64>()]
+}
+
+#[cfg(feature = "i128")]
+fn strategies_128() -> [BoxedStrategy<PrimValue>; 2] {
+    [value::<u128>(), value::<i128>()]
+}
+
+#[cfg(not(feature = "i128"))]
+fn strategies_128() -> [BoxedStrategy<PrimValue>; 0] {
+    []
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn value_casts(x in any_prim_value()) {
+            let kind = x.kind();
+            prop_assert_eq!(x.checked_cast_to(kind).map(|y| y.kind()), Ok(kind));
+        }
+
+        #[test]
+        fn saturating_matches_checked(x in any_prim::<f64>()) {
+            if let Some(y) = x.checked_as::<i32>() {
+                prop_assert_eq!(x.saturating_as::<i32>(), y);
+            }
+        }
+    }
+}
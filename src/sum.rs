@@ -0,0 +1,100 @@
+use core::iter::FromIterator;
+
+use cmp::abs;
+use AsPrim;
+
+/// A sum of values of any primitive types, accumulated in `f64` with
+/// Neumaier's compensated summation.
+///
+/// It keeps the rounding error of each addition, so the total does not
+/// drift when adding many values or values of very different magnitudes.
+///
+/// ```
+/// use asprim::CompensatedSum;
+///
+/// let mut sum = CompensatedSum::new();
+/// sum.push(1e100);
+/// sum.push(1u8);
+/// sum.push(-1e100f64);
+/// assert_eq!(sum.value(), 1.);
+///
+/// let sum: CompensatedSum = vec![0.1f32; 10].into_iter().collect();
+/// assert_eq!(sum.value(), 10. * 0.1f32 as f64);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct CompensatedSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl CompensatedSum {
+    /// Create an empty sum.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `x` to the sum.
+    #[inline]
+    pub fn push<T: AsPrim>(&mut self, x: T) {
+        let x = x.as_f64();
+        let t = self.sum + x;
+        if abs(self.sum) >= abs(x) {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// The sum of the values so far.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        // The compensation is NaN once the sum is infinite
+        if self.sum.is_finite() {
+            self.sum + self.compensation
+        } else {
+            self.sum
+        }
+    }
+}
+
+impl<T: AsPrim> Extend<T> for CompensatedSum {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl<T: AsPrim> FromIterator<T> for CompensatedSum {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut sum = Self::new();
+        sum.extend(iter);
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compensated() {
+        let mut sum = CompensatedSum::new();
+        let mut naive = 0.;
+        for _ in 0..1000 {
+            sum.push(0.1);
+            naive += 0.1;
+        }
+        assert_eq!(sum.value(), 100.);
+        assert!(naive != 100.);
+
+        let mut sum: CompensatedSum = [1u64 << 53, 1, 1].iter().copied().collect();
+        assert_eq!(sum.value(), 9007199254740994.);
+        sum.extend([f64::INFINITY].iter().copied());
+        assert_eq!(sum.value(), f64::INFINITY);
+        sum.push(f32::NEG_INFINITY);
+        assert!(sum.value().is_nan());
+    }
+}
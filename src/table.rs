@@ -0,0 +1,27 @@
+use AsPrim;
+
+/// Look up `x` in a table built with `cast_table!`, at the index
+/// `x.as_u8()`.
+///
+/// The table has 256 entries, so every index is in range and the lookup
+/// needs no bounds check.
+#[inline(always)]
+pub fn lookup_as<T: AsPrim, U: Copy>(table: &[U; 256], x: T) -> U {
+    table[x.as_u8() as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables() {
+        const INVERT: [u8; 256] = cast_table!(|x: u8| -> u8 { !x });
+        const HALF: [i16; 256] = cast_table!(|x: i8| -> i16 { x as i16 * 100 / 2 });
+        for x in 0..=255u8 {
+            assert_eq!(lookup_as(&INVERT, x), 255 - x);
+            assert_eq!(lookup_as(&HALF, x as i8), x as i8 as i16 * 50);
+        }
+        assert_eq!(lookup_as(&INVERT, 300u16), 255 - 44);
+    }
+}
@@ -0,0 +1,135 @@
+//! Conformance checks for `AsPrim` implementations.
+//!
+//! Requires the `test-util` feature.
+
+use AsPrim;
+
+macro_rules! verify {
+    ($x:expr, $method:ident $checked:ident $saturating:ident $t:ty) => {
+        verify_cast::<_, $t>($x, $x.$method(), $x.$checked(), $x.$saturating(),
+                             stringify!($t))
+    }
+}
+
+/// Check that `AsPrim` behaves consistently for each of the `samples`,
+/// following the rules in the trait documentation.
+///
+/// It checks that the generic and per-type methods agree, that checked and
+/// saturating casts agree whenever the value is in range, and that values
+/// round-trip through every primitive type that holds them exactly.
+///
+/// # Panics
+///
+/// Panics with a description of the first rule that is broken.
+///
+/// ```
+/// use asprim::test_util::verify_asprim_impl;
+///
+/// verify_asprim_impl(&[0u16, 1, 255, 256, u16::MAX]);
+/// verify_asprim_impl(&[0., -0.5, 1e10, f32::MAX, f32::NAN, f32::INFINITY]);
+/// ```
+pub fn verify_asprim_impl<T: AsPrim>(samples: &[T]) {
+    for &x in samples {
+        verify!(x, as_usize as_usize_checked as_usize_saturating usize);
+        verify!(x, as_isize as_isize_checked as_isize_saturating isize);
+        #[cfg(feature = "i128")]
+        verify!(x, as_u128 as_u128_checked as_u128_saturating u128);
+        #[cfg(feature = "i128")]
+        verify!(x, as_i128 as_i128_checked as_i128_saturating i128);
+        verify!(x, as_u64 as_u64_checked as_u64_saturating u64);
+        verify!(x, as_i64 as_i64_checked as_i64_saturating i64);
+        verify!(x, as_u32 as_u32_checked as_u32_saturating u32);
+        verify!(x, as_i32 as_i32_checked as_i32_saturating i32);
+        verify!(x, as_u16 as_u16_checked as_u16_saturating u16);
+        verify!(x, as_i16 as_i16_checked as_i16_saturating i16);
+        verify!(x, as_u8 as_u8_checked as_u8_saturating u8);
+        verify!(x, as_i8 as_i8_checked as_i8_saturating i8);
+        verify!(x, as_f32 as_f32_checked as_f32_saturating f32);
+        verify!(x, as_f64 as_f64_checked as_f64_saturating f64);
+        verify_exact(x, x.as_f32_exact(), "as_f32_exact");
+        verify_exact(x, x.as_f64_exact(), "as_f64_exact");
+        let identity = T::checked_cast_from(x);
+        assert!(same_option(identity, Some(x)),
+                "{:?}.checked_as::<Self>() is {:?}", x, identity);
+    }
+}
+
+/// Equality where NaN is equal to itself.
+#[allow(clippy::eq_op)]
+fn same<T: AsPrim>(a: T, b: T) -> bool {
+    a == b || (a != a && b != b)
+}
+
+fn same_option<T: AsPrim>(a: Option<T>, b: Option<T>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => same(a, b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn verify_exact<T: AsPrim, F: AsPrim>(x: T, exact: Option<F>, method: &str) {
+    if let Some(f) = exact {
+        assert!(same(f, x.as_()), "{:?}.{}() is {:?}, not the same as as_()", x, method, f);
+        assert!(same(T::cast_from(f), x),
+                "{:?}.{}() is {:?}, which does not cast back", x, method, f);
+    }
+}
+
+fn verify_cast<T: AsPrim, P: AsPrim>(x: T, lossy: P, checked: Option<P>, saturating: P,
+                                     name: &str)
+{
+    assert!(same(x.as_::<P>(), lossy), "{:?}.as_::<{}>() is not {:?}", x, name, lossy);
+    assert!(same_option(x.checked_as::<P>(), checked),
+            "{:?}.checked_as::<{}>() is not {:?}", x, name, checked);
+    assert!(same(x.saturating_as::<P>(), saturating),
+            "{:?}.saturating_as::<{}>() is not {:?}", x, name, saturating);
+    if let Some(y) = checked {
+        assert!(same(y, lossy),
+                "{:?} is in range for {}, but the checked cast {:?} is not the same as as_()",
+                x, name, y);
+        assert!(same(y, saturating),
+                "{:?} is in range for {}, but the saturating cast is {:?}, not {:?}",
+                x, name, saturating, y);
+        let holds_exactly = match (x.as_f64_exact(), y.as_f64_exact()) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        };
+        if holds_exactly {
+            let back = T::checked_cast_from(y);
+            assert!(same_option(back, Some(x)),
+                    "{:?} does not round-trip through {}, it came back as {:?}",
+                    x, name, back);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use AsPrim;
+    use super::verify_asprim_impl;
+
+    #[test]
+    fn primitives() {
+        verify_asprim_impl(&[0u8, 1, 127, 128, 255]);
+        verify_asprim_impl(&[0i8, 1, -1, 127, -128]);
+        verify_asprim_impl(&[0u32, 255, 256, 1 << 24, (1 << 24) + 1, u32::MAX]);
+        verify_asprim_impl(&[0i64, -1, i64::MIN, i64::MAX, 1 << 53, -(1 << 53) - 1]);
+        verify_asprim_impl(&[usize::MAX, isize::MIN as usize]);
+        #[cfg(feature = "i128")]
+        verify_asprim_impl(&[0u128, u128::MAX, u64::MAX as u128 + 1]);
+        verify_asprim_impl(&[0f32, -0., 0.5, -1.5, 255.5, 1e20, f32::MIN, f32::NAN,
+                             f32::NEG_INFINITY]);
+        verify_asprim_impl(&[0f64, 1e-300, 4294967295.9, -2147483648.9, 1e300, f64::NAN,
+                             f64::INFINITY]);
+        verify_asprim_impl(u8::BOUNDARY_VALUES);
+        verify_asprim_impl(i16::BOUNDARY_VALUES);
+        verify_asprim_impl(u32::BOUNDARY_VALUES);
+        verify_asprim_impl(i64::BOUNDARY_VALUES);
+        verify_asprim_impl(isize::BOUNDARY_VALUES);
+        #[cfg(feature = "i128")]
+        verify_asprim_impl(i128::BOUNDARY_VALUES);
+        verify_asprim_impl(f32::BOUNDARY_VALUES);
+        verify_asprim_impl(f64::BOUNDARY_VALUES);
+    }
+}
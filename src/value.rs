@@ -0,0 +1,448 @@
+//! Type-erased primitive values.
+
+use core::fmt;
+use core::mem::size_of;
+use core::str::FromStr;
+
+use AsPrim;
+
+/// The value behind `AsPrim::KIND`.
+pub trait KindOf {
+    const KIND: PrimKind;
+}
+
+macro_rules! prim_kinds {
+    ($($(#[$attr:meta])* $variant:ident $t:ident)*) => {
+        /// The primitive numeric types.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum PrimKind {
+            $($(#[$attr])* $variant,)*
+        }
+
+        /// A value of any primitive numeric type.
+        ///
+        /// ```
+        /// use asprim::{PrimKind, PrimValue};
+        ///
+        /// let x = PrimValue::from(300u16);
+        /// assert_eq!(x.kind(), PrimKind::U16);
+        /// assert_eq!(x.cast_to(PrimKind::U8), PrimValue::U8(44));
+        /// assert!(x.checked_cast_to(PrimKind::U8).is_err());
+        /// assert_eq!(x.as_::<f32>(), 300.);
+        /// ```
+        #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+        pub enum PrimValue {
+            $($(#[$attr])* $variant($t),)*
+        }
+
+        impl PrimKind {
+            /// All the kinds, in declaration order.
+            pub const ALL: &'static [PrimKind] = &[$($(#[$attr])* PrimKind::$variant,)*];
+
+            /// The size of the type in bits.
+            #[inline]
+            pub const fn bits(self) -> u32 {
+                match self {
+                    $($(#[$attr])* PrimKind::$variant => (size_of::<$t>() * 8) as u32,)*
+                }
+            }
+
+            /// The name of the variant, like `"U32"`.
+            #[cfg(feature = "ufmt")]
+            pub(crate) fn variant_name(self) -> &'static str {
+                match self {
+                    $($(#[$attr])* PrimKind::$variant => stringify!($variant),)*
+                }
+            }
+
+            /// The name of the type, like `"u32"`.
+            #[inline]
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $($(#[$attr])* PrimKind::$variant => stringify!($t),)*
+                }
+            }
+        }
+
+        impl PrimValue {
+            /// Wrap `x`, with the kind `P::KIND`.
+            #[inline]
+            pub fn of<P: AsPrim>(x: P) -> Self {
+                match P::KIND {
+                    $($(#[$attr])* PrimKind::$variant => PrimValue::$variant(x.as_()),)*
+                }
+            }
+
+            /// The type of the value.
+            #[inline]
+            pub fn kind(self) -> PrimKind {
+                match self {
+                    $($(#[$attr])* PrimValue::$variant(_) => PrimKind::$variant,)*
+                }
+            }
+
+            /// Cast the value to the type `T` using `as`.
+            #[inline]
+            pub fn as_<T: AsPrim>(self) -> T {
+                match self {
+                    $($(#[$attr])* PrimValue::$variant(x) => x.as_(),)*
+                }
+            }
+
+            /// Cast the value to the type `T`, or return `None` if it is out
+            /// of range, like `AsPrim::checked_as`.
+            #[inline]
+            pub fn checked_as<T: AsPrim>(self) -> Option<T> {
+                match self {
+                    $($(#[$attr])* PrimValue::$variant(x) => x.checked_as(),)*
+                }
+            }
+
+            /// Cast the value to the type `T`, clamping it to the range of
+            /// `T`, like `AsPrim::saturating_as`.
+            #[inline]
+            pub fn saturating_as<T: AsPrim>(self) -> T {
+                match self {
+                    $($(#[$attr])* PrimValue::$variant(x) => x.saturating_as(),)*
+                }
+            }
+
+            /// Cast the value to the type `kind` using `as`.
+            #[inline]
+            pub fn cast_to(self, kind: PrimKind) -> PrimValue {
+                match kind {
+                    $($(#[$attr])* PrimKind::$variant => PrimValue::$variant(self.as_()),)*
+                }
+            }
+
+            /// Cast the value to the type `kind`, or return an error if it
+            /// is out of range, like `AsPrim::checked_as`.
+            #[inline]
+            pub fn checked_cast_to(self, kind: PrimKind) -> Result<PrimValue, CastError> {
+                let value = match kind {
+                    $($(#[$attr])* PrimKind::$variant => {
+                        self.checked_as().map(PrimValue::$variant)
+                    })*
+                };
+                value.ok_or(CastError { value: self, target: kind })
+            }
+
+            /// Cast the value to the type `kind`, clamping it to the range
+            /// of the target, like `AsPrim::saturating_as`.
+            #[inline]
+            pub fn saturating_cast_to(self, kind: PrimKind) -> PrimValue {
+                match kind {
+                    $($(#[$attr])* PrimKind::$variant => {
+                        PrimValue::$variant(self.saturating_as())
+                    })*
+                }
+            }
+        }
+
+        impl fmt::Display for PrimValue {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match *self {
+                    $($(#[$attr])* PrimValue::$variant(ref x) => x.fmt(f),)*
+                }
+            }
+        }
+
+        $(
+            $(#[$attr])*
+            impl KindOf for $t {
+                const KIND: PrimKind = PrimKind::$variant;
+            }
+        )*
+
+        $(
+            $(#[$attr])*
+            impl From<$t> for PrimValue {
+                #[inline]
+                fn from(x: $t) -> Self {
+                    PrimValue::$variant(x)
+                }
+            }
+        )*
+    }
+}
+
+prim_kinds!{
+    U8 u8
+    I8 i8
+    U16 u16
+    I16 i16
+    U32 u32
+    I32 i32
+    U64 u64
+    I64 i64
+    #[cfg(feature = "i128")]
+    U128 u128
+    #[cfg(feature = "i128")]
+    I128 i128
+    Usize usize
+    Isize isize
+    F32 f32
+    F64 f64
+}
+
+impl PrimKind {
+    /// Return `true` for the float types.
+    #[inline]
+    pub const fn is_float(self) -> bool {
+        matches!(self, PrimKind::F32 | PrimKind::F64)
+    }
+
+    /// Return `true` for the signed integer and the float types.
+    #[inline]
+    pub const fn is_signed(self) -> bool {
+        match self {
+            PrimKind::U8 | PrimKind::U16 | PrimKind::U32 | PrimKind::U64 | PrimKind::Usize => false,
+            #[cfg(feature = "i128")]
+            PrimKind::U128 => false,
+            _ => true,
+        }
+    }
+
+    /// The number of value bits of an integer type, not counting the sign
+    /// bit.
+    #[inline]
+    pub(crate) const fn digits(self) -> u32 {
+        self.bits() - self.is_signed() as u32
+    }
+
+    /// The number of significant binary digits of a float type, including
+    /// the implicit leading bit; for an integer type, its size in bits.
+    #[inline]
+    pub(crate) const fn mantissa_digits(self) -> u32 {
+        match self {
+            PrimKind::F32 => f32::MANTISSA_DIGITS,
+            PrimKind::F64 => f64::MANTISSA_DIGITS,
+            _ => self.bits(),
+        }
+    }
+}
+
+/// Other names of the types, from NumPy: the sized names, and `single` and
+/// `double`, which C shares. Names whose type differs between C and NumPy
+/// or across C implementations, like `int`, `float` and `byte`, are left
+/// out.
+const ALIASES: &[(&str, PrimKind)] = &[
+    ("float32", PrimKind::F32),
+    ("single", PrimKind::F32),
+    ("double", PrimKind::F64),
+    ("float64", PrimKind::F64),
+    ("uint8", PrimKind::U8),
+    ("int8", PrimKind::I8),
+    ("uint16", PrimKind::U16),
+    ("int16", PrimKind::I16),
+    ("uint32", PrimKind::U32),
+    ("int32", PrimKind::I32),
+    ("uint64", PrimKind::U64),
+    ("int64", PrimKind::I64),
+    #[cfg(feature = "i128")]
+    ("uint128", PrimKind::U128),
+    #[cfg(feature = "i128")]
+    ("int128", PrimKind::I128),
+    ("uintp", PrimKind::Usize),
+    ("intp", PrimKind::Isize),
+];
+
+/// The C names of the types, from `<stdint.h>` and `<stddef.h>`; the first
+/// name of each kind is its `c_name`.
+const C_NAMES: &[(&str, PrimKind)] = &[
+    ("uint8_t", PrimKind::U8),
+    ("int8_t", PrimKind::I8),
+    ("uint16_t", PrimKind::U16),
+    ("int16_t", PrimKind::I16),
+    ("uint32_t", PrimKind::U32),
+    ("int32_t", PrimKind::I32),
+    ("uint64_t", PrimKind::U64),
+    ("int64_t", PrimKind::I64),
+    #[cfg(feature = "i128")]
+    ("unsigned __int128", PrimKind::U128),
+    #[cfg(feature = "i128")]
+    ("__int128", PrimKind::I128),
+    ("uintptr_t", PrimKind::Usize),
+    ("intptr_t", PrimKind::Isize),
+    ("float", PrimKind::F32),
+    ("double", PrimKind::F64),
+    #[cfg(feature = "i128")]
+    ("__uint128_t", PrimKind::U128),
+    #[cfg(feature = "i128")]
+    ("__int128_t", PrimKind::I128),
+    ("size_t", PrimKind::Usize),
+    ("ssize_t", PrimKind::Isize),
+    ("ptrdiff_t", PrimKind::Isize),
+];
+
+impl PrimKind {
+    /// The name of the type in C, like `"uint32_t"` or `"double"`.
+    ///
+    /// `usize` and `isize` are `uintptr_t` and `intptr_t`, and the 128-bit
+    /// integers the GCC and Clang extension `unsigned __int128` and
+    /// `__int128`.
+    ///
+    /// ```
+    /// use asprim::PrimKind;
+    ///
+    /// assert_eq!(PrimKind::U32.c_name(), "uint32_t");
+    /// assert_eq!(PrimKind::F64.c_name(), "double");
+    /// ```
+    pub fn c_name(self) -> &'static str {
+        C_NAMES.iter()
+            .find(|&&(_, kind)| kind == self)
+            .map(|&(name, _)| name)
+            .unwrap()
+    }
+
+    /// Look up a type by its C name, the inverse of `c_name`.
+    ///
+    /// Also accepted are `size_t`, `ssize_t` and `ptrdiff_t`, and the 128-bit
+    /// integers `__uint128_t` and `__int128_t`. Names whose size depends on
+    /// the C implementation, like `int` and `long`, are not.
+    ///
+    /// ```
+    /// use asprim::PrimKind;
+    ///
+    /// assert_eq!(PrimKind::from_c_name("int16_t"), Ok(PrimKind::I16));
+    /// assert_eq!(PrimKind::from_c_name("size_t"), Ok(PrimKind::Usize));
+    /// assert!(PrimKind::from_c_name("long").is_err());
+    ///
+    /// for &kind in PrimKind::ALL {
+    ///     assert_eq!(PrimKind::from_c_name(kind.c_name()), Ok(kind));
+    /// }
+    /// ```
+    pub fn from_c_name(name: &str) -> Result<PrimKind, ParseKindError> {
+        C_NAMES.iter()
+            .find(|&&(c_name, _)| c_name == name)
+            .map(|&(_, kind)| kind)
+            .ok_or(ParseKindError(()))
+    }
+}
+
+/// The error of parsing a `PrimKind` from an unknown name.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseKindError(pub(crate) ());
+
+impl fmt::Display for ParseKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("unknown primitive type name")
+    }
+}
+
+/// Parse the name of a type, like `"u32"`, or one of the common aliases:
+/// `"single"` and `"double"`, and the NumPy names like `"int64"` and
+/// `"float32"`. Letter case is ignored.
+///
+/// Names whose type depends on the convention are rejected, like in
+/// `PrimKind::from_c_name`: the C implementation sizes `"int"` and
+/// `"short"`, NumPy's `"float"` is `f64` where C's is `f32`, and NumPy's
+/// `"byte"` is signed.
+///
+/// ```
+/// use asprim::PrimKind;
+///
+/// assert_eq!("u32".parse(), Ok(PrimKind::U32));
+/// assert_eq!("double".parse(), Ok(PrimKind::F64));
+/// assert_eq!("Int64".parse(), Ok(PrimKind::I64));
+/// assert!("u7".parse::<PrimKind>().is_err());
+/// assert!("int".parse::<PrimKind>().is_err());
+///
+/// for &kind in PrimKind::ALL {
+///     assert_eq!(kind.name().parse(), Ok(kind));
+/// }
+/// ```
+impl FromStr for PrimKind {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, ParseKindError> {
+        let names = PrimKind::ALL.iter().map(|&kind| (kind.name(), kind));
+        names.chain(ALIASES.iter().cloned())
+            .find(|&(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(_, kind)| kind)
+            .ok_or(ParseKindError(()))
+    }
+}
+
+/// The error of a checked cast of a `PrimValue` that is out of range.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CastError {
+    value: PrimValue,
+    target: PrimKind,
+}
+
+impl CastError {
+    #[inline]
+    pub(crate) fn new(value: PrimValue, target: PrimKind) -> Self {
+        CastError { value, target }
+    }
+
+    /// The value that did not fit.
+    pub fn value(&self) -> PrimValue {
+        self.value
+    }
+
+    /// The type it did not fit.
+    pub fn target(&self) -> PrimKind {
+        self.target
+    }
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:?}) is out of range for {:?}", self.value, self.value.kind(),
+               self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casts() {
+        for &kind in PrimKind::ALL {
+            let one = PrimValue::from(1u8).cast_to(kind);
+            assert_eq!(one.kind(), kind);
+            assert_eq!(one.checked_cast_to(kind), Ok(one));
+            assert_eq!(one.as_::<i32>(), 1);
+        }
+        let x = PrimValue::from(-1.5f64);
+        assert_eq!(x.saturating_cast_to(PrimKind::U32), PrimValue::U32(0));
+        assert_eq!(x.cast_to(PrimKind::I8), PrimValue::I8(-1));
+        let err = x.checked_cast_to(PrimKind::U8).unwrap_err();
+        assert_eq!(err.value(), x);
+        assert_eq!(err.target(), PrimKind::U8);
+    }
+
+    #[test]
+    fn parse_kind() {
+        assert_eq!("F32".parse(), Ok(PrimKind::F32));
+        assert_eq!("intp".parse(), Ok(PrimKind::Isize));
+        assert_eq!("".parse::<PrimKind>(), Err(ParseKindError(())));
+        assert!(" u8".parse::<PrimKind>().is_err());
+        assert_eq!("Single".parse(), Ok(PrimKind::F32));
+        for name in &["byte", "short", "int", "float", "long"] {
+            assert!(name.parse::<PrimKind>().is_err(), "{}", name);
+        }
+        for &(alias, _) in ALIASES {
+            assert!(PrimKind::ALL.iter().all(|kind| kind.name() != alias));
+        }
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(PrimKind::Usize.name(), "usize");
+        assert_eq!(<i16 as AsPrim>::NAME, "i16");
+        assert_eq!(<f32 as AsPrim>::NAME, "f32");
+    }
+
+    #[test]
+    fn c_names() {
+        assert_eq!(PrimKind::Isize.c_name(), "intptr_t");
+        assert_eq!(PrimKind::from_c_name("ptrdiff_t"), Ok(PrimKind::Isize));
+        assert!(PrimKind::from_c_name("UINT8_T").is_err());
+        #[cfg(feature = "i128")]
+        assert_eq!(PrimKind::from_c_name("__uint128_t"), Ok(PrimKind::U128));
+    }
+}
@@ -0,0 +1,186 @@
+//! LEB128 variable-length integers and the zigzag transform, for integers
+//! of any width.
+//!
+//! A varint holds seven bits of the value in each byte, least significant
+//! first, and sets the high bit of every byte but the last. Small values
+//! take few bytes, but negative values of a signed type take the most,
+//! since all their high bits are set; zigzag them first to keep small
+//! negative values short, like protobuf's `sint` types do.
+//!
+//! ```
+//! use asprim::varint::{decode_varint, encode_varint, zigzag_decode, zigzag_encode};
+//!
+//! let mut buf = [0; 16];
+//! let mut n = encode_varint(300u16, &mut buf);
+//! n += encode_varint(zigzag_encode(-2i64), &mut buf[n..]);
+//! assert_eq!(buf[..n], [0xac, 0x02, 0x03]);
+//!
+//! let (x, len) = decode_varint::<u16>(&buf).unwrap();
+//! assert_eq!(x, 300);
+//! let (z, _) = decode_varint::<u64>(&buf[len..n]).unwrap();
+//! assert_eq!(zigzag_decode(z), -2);
+//! ```
+
+use core::mem;
+
+use {AsPrim, DecodeError, PrimInt, Signed, Unsigned};
+
+/// The most bytes that `encode_varint` writes, for a 128-bit value.
+pub const MAX_VARINT_LEN: usize = 19;
+
+/// The bits of `x` as an unsigned integer of the widest type.
+#[inline]
+fn to_bits<T: PrimInt>(x: T) -> Unsigned {
+    x.as_::<T::Unsigned>().as_()
+}
+
+/// The number of bytes that `encode_varint` writes for `value`.
+///
+/// ```
+/// use asprim::varint::varint_len;
+///
+/// assert_eq!(varint_len(127u8), 1);
+/// assert_eq!(varint_len(128u8), 2);
+/// assert_eq!(varint_len(-1i32), 5);
+/// ```
+#[inline]
+pub fn varint_len<T: PrimInt>(value: T) -> usize {
+    let used = 8 * mem::size_of::<Unsigned>() - to_bits(value).leading_zeros() as usize;
+    if used == 0 { 1 } else { (used + 6) / 7 }
+}
+
+/// Write `value` to the start of `buf` as an unsigned LEB128 varint, and
+/// return the number of bytes written.
+///
+/// A signed value is written as the unsigned integer with the same bits,
+/// so `-1i32` takes five bytes; see `zigzag_encode`.
+///
+/// ***Panics*** if `buf` is shorter than `varint_len(value)`.
+///
+/// ```
+/// use asprim::varint::encode_varint;
+///
+/// let mut buf = [0; 5];
+/// assert_eq!(encode_varint(1u64, &mut buf), 1);
+/// assert_eq!(encode_varint(-1i32, &mut buf), 5);
+/// assert_eq!(buf, [0xff, 0xff, 0xff, 0xff, 0x0f]);
+/// ```
+pub fn encode_varint<T: PrimInt>(value: T, buf: &mut [u8]) -> usize {
+    let len = varint_len(value);
+    assert!(buf.len() >= len, "encode_varint: buffer is too short");
+    let mut bits = to_bits(value);
+    for byte in &mut buf[..len - 1] {
+        *byte = bits as u8 | 0x80;
+        bits >>= 7;
+    }
+    buf[len - 1] = bits as u8;
+    len
+}
+
+/// Read a varint written by `encode_varint` from the start of `buf`, and
+/// return it with the number of bytes read.
+///
+/// The value must fit the width of `T`, and a signed `T` takes it as the
+/// bits of a negative value like `encode_varint` writes them. Padding with
+/// extra zero bytes is accepted up to the longest encoding of `T`.
+///
+/// ```
+/// use asprim::DecodeError;
+/// use asprim::varint::decode_varint;
+///
+/// assert_eq!(decode_varint::<u32>(&[0xac, 0x02, 0xff]), Ok((300, 2)));
+/// assert_eq!(decode_varint::<i8>(&[0xff, 0x01]), Ok((-1, 2)));
+/// assert_eq!(decode_varint::<u8>(&[0x80, 0x02]), Err(DecodeError::OutOfRange));
+/// assert_eq!(decode_varint::<u8>(&[0x80]), Err(DecodeError::Truncated));
+/// ```
+pub fn decode_varint<T: PrimInt>(buf: &[u8]) -> Result<(T, usize), DecodeError> {
+    let width = T::KIND.bits();
+    let mut bits: Unsigned = 0;
+    for (i, &byte) in buf.iter().enumerate() {
+        let shift = 7 * i as u32;
+        let payload = (byte & 0x7f) as Unsigned;
+        if shift >= width || width - shift < 7 && payload >> (width - shift) != 0 {
+            return Err(DecodeError::OutOfRange);
+        }
+        bits |= payload << shift;
+        if byte & 0x80 == 0 {
+            return Ok((bits.as_::<T::Unsigned>().as_(), i + 1));
+        }
+    }
+    Err(DecodeError::Truncated)
+}
+
+/// Map a signed integer to an unsigned one of the same width, so that
+/// values close to zero are small: 0, -1, 1, -2 become 0, 1, 2, 3.
+///
+/// An unsigned `x` is taken as the signed value with the same bits.
+///
+/// ```
+/// use asprim::varint::zigzag_encode;
+///
+/// assert_eq!(zigzag_encode(-1i8), 1u8);
+/// assert_eq!(zigzag_encode(i32::MIN), u32::MAX);
+/// assert_eq!(zigzag_encode(0xffu8), 1);
+/// ```
+#[inline]
+pub fn zigzag_encode<T: PrimInt>(x: T) -> T::Unsigned {
+    // Sign extend to the widest type, where the zigzag of a narrower value
+    // is the same and fits its width
+    let x: Signed = x.as_::<T::Signed>().as_();
+    let z = (x << 1) ^ (x >> (8 * mem::size_of::<Signed>() - 1));
+    (z as Unsigned).as_()
+}
+
+/// Map an unsigned integer back to the signed value that `zigzag_encode`
+/// mapped to it.
+///
+/// A signed `z` is taken as the unsigned value with the same bits.
+///
+/// ```
+/// use asprim::varint::zigzag_decode;
+///
+/// assert_eq!(zigzag_decode(3u16), -2i16);
+/// assert_eq!(zigzag_decode(u64::MAX), i64::MIN);
+/// ```
+#[inline]
+pub fn zigzag_decode<T: PrimInt>(z: T) -> T::Signed {
+    let z = to_bits(z);
+    ((z >> 1) as Signed ^ -((z & 1) as Signed)).as_()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: PrimInt>(x: T) {
+        let mut buf = [0; MAX_VARINT_LEN];
+        let n = encode_varint(x, &mut buf);
+        assert_eq!(n, varint_len(x));
+        assert_eq!(decode_varint::<T>(&buf[..n]), Ok((x, n)));
+        assert_eq!(decode_varint::<T>(&buf[..n - 1]), Err(DecodeError::Truncated));
+        assert_eq!(zigzag_decode(zigzag_encode(x)).as_::<T>(), x);
+    }
+
+    #[test]
+    fn varint() {
+        for &x in i64::BOUNDARY_VALUES {
+            round_trip(x);
+            round_trip(x as u16);
+            round_trip(x as isize);
+        }
+        #[cfg(feature = "i128")]
+        {
+            round_trip(u128::MAX);
+            round_trip(i128::MIN);
+            assert_eq!(varint_len(u128::MAX), MAX_VARINT_LEN);
+        }
+        assert_eq!(varint_len(0u8), 1);
+        assert_eq!(decode_varint::<u16>(&[0xff, 0xff, 0x03]), Ok((u16::MAX, 3)));
+        assert_eq!(decode_varint::<u16>(&[0xff, 0xff, 0x04]), Err(DecodeError::OutOfRange));
+        assert_eq!(decode_varint::<u16>(&[0x81, 0x80, 0x00]), Ok((1, 3)));
+        assert_eq!(decode_varint::<u16>(&[0x81, 0x80, 0x80, 0x00]), Err(DecodeError::OutOfRange));
+        assert_eq!(decode_varint::<u16>(&[]), Err(DecodeError::Truncated));
+        assert_eq!(zigzag_encode(i16::MAX), u16::MAX - 1);
+        assert_eq!(zigzag_decode(i8::MIN), 64);
+    }
+}
@@ -0,0 +1,149 @@
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice;
+
+use AsPrim;
+
+/// A view of a slice with every element cast to the type `U` using `as`,
+/// when it is read.
+///
+/// Nothing is converted up front and nothing is allocated, so reading a few
+/// elements of a large slice only costs those casts.
+///
+/// ```
+/// use asprim::CastView;
+///
+/// let samples = [0i16, 16384, -32768];
+/// let view = CastView::<_, f32>::new(&samples);
+/// assert_eq!(view.len(), 3);
+/// assert_eq!(view.get(1), Some(16384.));
+/// assert_eq!(view.get(3), None);
+/// let scaled: Vec<f32> = view.iter().map(|x| x / 32768.).collect();
+/// assert_eq!(scaled, [0., 0.5, -1.]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct CastView<'a, T: 'a, U> {
+    src: &'a [T],
+    dst: PhantomData<fn() -> U>,
+}
+
+impl<'a, T: AsPrim, U: AsPrim> CastView<'a, T, U> {
+    /// Create a view of `src`.
+    #[inline]
+    pub fn new(src: &'a [T]) -> Self {
+        CastView { src, dst: PhantomData }
+    }
+
+    /// The number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.src.len()
+    }
+
+    /// Return `true` if the view has no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.src.is_empty()
+    }
+
+    /// The element at `index` cast to `U`, or `None` if it is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<U> {
+        self.src.get(index).map(|&x| x.as_())
+    }
+
+    /// The first element cast to `U`, or `None` if the view is empty.
+    #[inline]
+    pub fn first(&self) -> Option<U> {
+        self.get(0)
+    }
+
+    /// The last element cast to `U`, or `None` if the view is empty.
+    #[inline]
+    pub fn last(&self) -> Option<U> {
+        self.src.last().map(|&x| x.as_())
+    }
+
+    /// Iterate over the elements cast to `U`.
+    #[inline]
+    pub fn iter(&self) -> CastIter<'a, T, U> {
+        CastIter { iter: self.src.iter(), dst: PhantomData }
+    }
+
+    /// The viewed slice.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        self.src
+    }
+}
+
+impl<'a, T: AsPrim, U: AsPrim> IntoIterator for CastView<'a, T, U> {
+    type Item = U;
+    type IntoIter = CastIter<'a, T, U>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a slice cast to the type `U`, created
+/// by `CastView::iter`.
+#[derive(Clone, Debug)]
+pub struct CastIter<'a, T: 'a, U> {
+    iter: slice::Iter<'a, T>,
+    dst: PhantomData<fn() -> U>,
+}
+
+impl<'a, T: AsPrim, U: AsPrim> Iterator for CastIter<'a, T, U> {
+    type Item = U;
+
+    #[inline]
+    fn next(&mut self) -> Option<U> {
+        self.iter.next().map(|&x| x.as_())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<U> {
+        self.iter.nth(n).map(|&x| x.as_())
+    }
+}
+
+impl<'a, T: AsPrim, U: AsPrim> DoubleEndedIterator for CastIter<'a, T, U> {
+    #[inline]
+    fn next_back(&mut self) -> Option<U> {
+        self.iter.next_back().map(|&x| x.as_())
+    }
+}
+
+impl<'a, T: AsPrim, U: AsPrim> ExactSizeIterator for CastIter<'a, T, U> {}
+
+impl<'a, T: AsPrim, U: AsPrim> FusedIterator for CastIter<'a, T, U> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view() {
+        let xs = [1.5f64, -1., 7., 300.];
+        let view = CastView::<_, u8>::new(&xs);
+        assert_eq!((view.first(), view.last()), (Some(1), Some(255)));
+        let mut iter = view.into_iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(255));
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.nth(1), Some(7));
+        assert_eq!(iter.next(), None);
+        let empty = CastView::<u8, u8>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.last(), None);
+        assert_eq!(view.as_slice().len(), 4);
+    }
+}
@@ -0,0 +1,136 @@
+//! Wrappers that implement the standard conversion traits.
+
+use core::convert::TryFrom;
+
+use {AsPrim, CastError, PrimValue};
+
+/// A value of the type `T`, converted with `TryFrom` from any primitive
+/// numeric type using `checked_as`.
+///
+/// The standard traits can not be implemented between the primitives
+/// themselves, so the wrapper carries the crate's checked casts to code
+/// written against `TryFrom` and `TryInto`.
+///
+/// ```
+/// use std::convert::{TryFrom, TryInto};
+/// use asprim::Checked;
+///
+/// assert_eq!(Checked::<u8>::try_from(255.5f64).unwrap().0, 255);
+/// assert!(Checked::<u8>::try_from(-1i32).is_err());
+///
+/// let x: Result<Checked<i16>, _> = 40000u32.try_into();
+/// assert_eq!(x.unwrap_err().to_string(), "40000 (U32) is out of range for I16");
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+impl<T> Checked<T> {
+    /// Return the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! checked_try_from {
+    ($($(#[$attr:meta])* $t:ident)*) => {
+        $(
+            $(#[$attr])*
+            impl<T: AsPrim> TryFrom<$t> for Checked<T> {
+                type Error = CastError;
+
+                #[inline]
+                fn try_from(x: $t) -> Result<Self, CastError> {
+                    match x.checked_as() {
+                        Some(y) => Ok(Checked(y)),
+                        None => Err(CastError::new(PrimValue::from(x), T::KIND)),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+checked_try_from!{
+    u8 i8 u16 i16 u32 i32 u64 i64
+    #[cfg(feature = "i128")] u128
+    #[cfg(feature = "i128")] i128
+    usize isize f32 f64
+}
+
+/// A value of the type `T`, converted with `From` from any primitive
+/// numeric type using `as`.
+///
+/// This is the infallible counterpart of `Checked`, for code written
+/// against `From` and `Into`.
+///
+/// ```
+/// use asprim::Lossy;
+///
+/// let x: Lossy<u8> = 300i32.into();
+/// assert_eq!(x.0, 44);
+///
+/// fn level<L: Into<Lossy<f32>>>(l: L) -> f32 {
+///     l.into().0
+/// }
+/// assert_eq!(level(2u64), 2.);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lossy<T>(pub T);
+
+impl<T> Lossy<T> {
+    /// Return the wrapped value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+macro_rules! lossy_from {
+    ($($(#[$attr:meta])* $t:ident)*) => {
+        $(
+            $(#[$attr])*
+            impl<T: AsPrim> From<$t> for Lossy<T> {
+                #[inline]
+                fn from(x: $t) -> Self {
+                    Lossy(x.as_())
+                }
+            }
+        )*
+    }
+}
+
+lossy_from!{
+    u8 i8 u16 i16 u32 i32 u64 i64
+    #[cfg(feature = "i128")] u128
+    #[cfg(feature = "i128")] i128
+    usize isize f32 f64
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::TryInto;
+
+    use super::*;
+    use PrimKind;
+
+    #[test]
+    fn try_from() {
+        let x: Checked<f32> = 16777217u32.try_into().unwrap();
+        assert_eq!(x.into_inner(), 16777216.);
+        let err = Checked::<u64>::try_from(f64::NAN).unwrap_err();
+        assert_eq!(err.target(), PrimKind::U64);
+        assert!(Checked::<f32>::try_from(f64::MAX).is_err());
+        assert_eq!(Checked::<i8>::try_from(-128i64), Ok(Checked(-128)));
+    }
+
+    #[test]
+    fn from() {
+        assert_eq!(Lossy::<u8>::from(-1i8).into_inner(), 255);
+        assert_eq!(Lossy::<u8>::from(-1.5f64), Lossy(0));
+        assert_eq!(Lossy::<i16>::from(f32::NAN), Lossy(0));
+        let x: Lossy<f64> = usize::MAX.into();
+        assert_eq!(x.0, usize::MAX as f64);
+        assert_eq!(Lossy::<u16>::from(70000u32), Lossy(4464));
+    }
+}
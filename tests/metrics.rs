@@ -0,0 +1,55 @@
+#![cfg(feature = "metrics")]
+
+extern crate asprim;
+
+use asprim::metrics::{self, Snapshot};
+use asprim::{saturating_cast_slice_into, AsPrim, Be, PrimInt, PrimKind};
+
+/// Reset the counters, run `f`, and return the counts it made.
+fn counts<F: FnOnce()>(f: F) -> Snapshot {
+    metrics::reset();
+    f();
+    metrics::snapshot()
+}
+
+fn assert_counts(c: Snapshot, kind: PrimKind, saturated: usize, failed: usize) {
+    assert_eq!((c.saturated(kind), c.failed(kind)), (saturated, failed), "{:?}", kind);
+    assert_eq!((c.total_saturated(), c.total_failed()), (saturated, failed), "{:?}", kind);
+}
+
+// The counters are global, so the checks are in one test, in their own
+// test binary, where no other test casts while they run.
+#[test]
+fn counts_each_cast_once() {
+    // Generic casts
+    assert_counts(counts(|| assert_eq!(1e40f64.saturating_as::<f32>(), f32::MAX)),
+                  PrimKind::F32, 1, 0);
+    assert_counts(counts(|| assert_eq!(f64::NAN.saturating_as::<i16>(), 0)), PrimKind::I16, 1, 0);
+    assert_counts(counts(|| assert_eq!(2.5f64.saturating_as::<i16>(), 2)), PrimKind::I16, 0, 0);
+    assert_counts(counts(|| assert!(f64::NAN.saturating_as::<f32>().is_nan())),
+                  PrimKind::F32, 0, 0);
+    assert_counts(counts(|| assert_eq!(f64::INFINITY.checked_as::<f32>(), Some(f32::INFINITY))),
+                  PrimKind::F32, 0, 0);
+    assert_counts(counts(|| assert_eq!(70000.checked_as::<i16>(), None)), PrimKind::I16, 0, 1);
+
+    // Per-type methods, and the casts built on them
+    assert_counts(counts(|| assert_eq!(300.as_u8_saturating(), u8::MAX)), PrimKind::U8, 1, 0);
+    assert_counts(counts(|| assert_eq!((-1i64).as_u8_checked(), None)), PrimKind::U8, 0, 1);
+    assert_counts(counts(|| assert_eq!(u16::saturating_cast_from(-2.5f32), 0)),
+                  PrimKind::U16, 1, 0);
+    assert_counts(counts(|| assert_eq!(u16::checked_cast_from(u64::MAX), None)),
+                  PrimKind::U16, 0, 1);
+    assert_counts(counts(|| assert_eq!(Be::<u8>::saturating_cast_from(300).get(), 255)),
+                  PrimKind::U8, 1, 0);
+
+    // Range checks inside other operations
+    assert_counts(counts(|| assert_eq!(u64::MAX.as_f64_exact(), None)), PrimKind::U64, 0, 0);
+    assert_counts(counts(|| assert_eq!(i64::from_f64_exact(1e300), None)), PrimKind::I64, 0, 0);
+    assert_counts(counts(|| {
+        let mut dst = [0u8; 3];
+        assert_eq!(saturating_cast_slice_into(&[1000, 1, 2], &mut dst), 1);
+    }), PrimKind::U8, 1, 0);
+    #[cfg(feature = "serde_json")]
+    assert_counts(counts(|| assert!(asprim::json::to_number(-3i8).is_some())),
+                  PrimKind::U64, 0, 0);
+}